@@ -0,0 +1,404 @@
+use std::{path::PathBuf, time::Duration};
+
+use app_review_crawler::{
+    review_crawler::{app_store::APP_STORE_MAX_PAGES, play_store::GOOGLE_PLAY_MAX_PAGES},
+    runner::{
+        RunSettings, StoreOptions, APP_STORE_CONCURRENCY, APP_STORE_RATE_LIMIT, APP_STORE_RETRY,
+        GOOGLE_PLAY_CONCURRENCY, GOOGLE_PLAY_RATE_LIMIT, GOOGLE_PLAY_RETRY,
+    },
+    RateLimit, RetryPolicy, SinkConfig, Store,
+};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "app-review-crawler",
+    version,
+    about = "Crawl app reviews from the Apple App Store and Google Play Store"
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    #[command(flatten)]
+    pub options: Options,
+}
+
+/// Flags shared by every subcommand.
+#[derive(Debug, Args)]
+pub struct Options {
+    /// Target apps config file
+    #[arg(long, short, global = true, default_value = "target_apps.json")]
+    pub config: PathBuf,
+
+    /// Directory the review files are written to
+    #[arg(long, short, global = true, default_value = "output")]
+    pub output_dir: PathBuf,
+
+    /// Last App Store page to request per app
+    #[arg(long, global = true, default_value_t = APP_STORE_MAX_PAGES)]
+    pub app_store_max_pages: u32,
+
+    /// Last Play Store page to request per app
+    #[arg(long, global = true, default_value_t = GOOGLE_PLAY_MAX_PAGES)]
+    pub play_store_max_pages: u32,
+
+    /// App Store apps crawled at once
+    #[arg(long, global = true, default_value_t = APP_STORE_CONCURRENCY)]
+    pub app_store_concurrency: usize,
+
+    /// Play Store apps crawled at once
+    #[arg(long, global = true, default_value_t = GOOGLE_PLAY_CONCURRENCY)]
+    pub play_store_concurrency: usize,
+
+    /// App Store requests per second, shared by every App Store app
+    #[arg(
+        long,
+        global = true,
+        default_value_t = APP_STORE_RATE_LIMIT.requests_per_second,
+        value_parser = parse_rate,
+    )]
+    pub app_store_rate: f64,
+
+    /// App Store requests sent back to back after an idle period
+    #[arg(
+        long,
+        global = true,
+        default_value_t = APP_STORE_RATE_LIMIT.burst,
+        value_parser = clap::value_parser!(u32).range(1..),
+    )]
+    pub app_store_burst: u32,
+
+    /// Play Store requests per second, shared by every Play Store app
+    #[arg(
+        long,
+        global = true,
+        default_value_t = GOOGLE_PLAY_RATE_LIMIT.requests_per_second,
+        value_parser = parse_rate,
+    )]
+    pub play_store_rate: f64,
+
+    /// Play Store requests sent back to back after an idle period
+    #[arg(
+        long,
+        global = true,
+        default_value_t = GOOGLE_PLAY_RATE_LIMIT.burst,
+        value_parser = clap::value_parser!(u32).range(1..),
+    )]
+    pub play_store_burst: u32,
+
+    /// Attempts per App Store page, including the first one
+    #[arg(
+        long,
+        global = true,
+        default_value_t = APP_STORE_RETRY.max_attempts,
+        value_parser = clap::value_parser!(u32).range(1..),
+    )]
+    pub app_store_max_attempts: u32,
+
+    /// Seconds before the first App Store retry; doubled on every further retry
+    #[arg(
+        long,
+        global = true,
+        default_value_t = APP_STORE_RETRY.base_delay.as_secs_f64(),
+        value_parser = parse_seconds,
+    )]
+    pub app_store_retry_base_delay: f64,
+
+    /// Upper bound in seconds for any single App Store retry delay
+    #[arg(
+        long,
+        global = true,
+        default_value_t = APP_STORE_RETRY.max_delay.as_secs_f64(),
+        value_parser = parse_seconds,
+    )]
+    pub app_store_retry_max_delay: f64,
+
+    /// Attempts per Play Store page, including the first one
+    #[arg(
+        long,
+        global = true,
+        default_value_t = GOOGLE_PLAY_RETRY.max_attempts,
+        value_parser = clap::value_parser!(u32).range(1..),
+    )]
+    pub play_store_max_attempts: u32,
+
+    /// Seconds before the first Play Store retry; doubled on every further retry
+    #[arg(
+        long,
+        global = true,
+        default_value_t = GOOGLE_PLAY_RETRY.base_delay.as_secs_f64(),
+        value_parser = parse_seconds,
+    )]
+    pub play_store_retry_base_delay: f64,
+
+    /// Upper bound in seconds for any single Play Store retry delay
+    #[arg(
+        long,
+        global = true,
+        default_value_t = GOOGLE_PLAY_RETRY.max_delay.as_secs_f64(),
+        value_parser = parse_seconds,
+    )]
+    pub play_store_retry_max_delay: f64,
+
+    /// Fail an app at its first failed page instead of skipping the page
+    #[arg(long, global = true)]
+    pub fail_fast: bool,
+
+    /// Log level (error, warn, info, debug, trace)
+    #[arg(long, global = true, default_value = "info")]
+    pub log_level: tracing::Level,
+}
+
+impl Options {
+    pub fn app_store_rate_limit(&self) -> RateLimit {
+        RateLimit {
+            requests_per_second: self.app_store_rate,
+            burst: self.app_store_burst,
+        }
+    }
+
+    pub fn play_store_rate_limit(&self) -> RateLimit {
+        RateLimit {
+            requests_per_second: self.play_store_rate,
+            burst: self.play_store_burst,
+        }
+    }
+
+    pub fn app_store_retry(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: self.app_store_max_attempts,
+            base_delay: Duration::from_secs_f64(self.app_store_retry_base_delay),
+            max_delay: Duration::from_secs_f64(self.app_store_retry_max_delay),
+            ..APP_STORE_RETRY
+        }
+    }
+
+    pub fn play_store_retry(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: self.play_store_max_attempts,
+            base_delay: Duration::from_secs_f64(self.play_store_retry_base_delay),
+            max_delay: Duration::from_secs_f64(self.play_store_retry_max_delay),
+            ..GOOGLE_PLAY_RETRY
+        }
+    }
+
+    /// Settings of a crawl run into `sinks`.
+    pub fn run_settings(&self, sinks: Vec<SinkConfig>) -> RunSettings {
+        RunSettings {
+            output_dir: self.output_dir.clone(),
+            sinks,
+            partial_success: !self.fail_fast,
+            app_store: StoreOptions {
+                concurrency: self.app_store_concurrency,
+                max_pages: self.app_store_max_pages,
+                retry: self.app_store_retry(),
+                rate_limit: self.app_store_rate_limit(),
+            },
+            play_store: StoreOptions {
+                concurrency: self.play_store_concurrency,
+                max_pages: self.play_store_max_pages,
+                retry: self.play_store_retry(),
+                rate_limit: self.play_store_rate_limit(),
+            },
+        }
+    }
+}
+
+// 0, 음수, NaN 같은 값은 인자 단계에서 거절
+fn parse_rate(value: &str) -> Result<f64, String> {
+    let rate: f64 = value.parse().map_err(|e| format!("{e}"))?;
+    RateLimit {
+        requests_per_second: rate,
+        burst: 1,
+    }
+    .validate()
+    .map_err(|e| e.to_string())?;
+    Ok(rate)
+}
+
+// Duration으로 바꿀 수 있는 0 이상의 초만 허용
+fn parse_seconds(value: &str) -> Result<f64, String> {
+    let seconds: f64 = value.parse().map_err(|e| format!("{e}"))?;
+    Duration::try_from_secs_f64(seconds)
+        .map_err(|_| format!("invalid number of seconds: {value}"))?;
+    Ok(seconds)
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Crawl every app in the config file (the default)
+    Crawl {
+        /// Only crawl these stores (repeatable; defaults to all)
+        #[arg(long = "store", value_enum)]
+        stores: Vec<StoreArg>,
+    },
+    /// Check the config file and exit
+    ValidateConfig,
+    /// Print the apps in the config file
+    ListApps {
+        /// Only list these stores (repeatable; defaults to all)
+        #[arg(long = "store", value_enum)]
+        stores: Vec<StoreArg>,
+    },
+    /// Crawl a single app into the config file's sinks (CSV only without a config file)
+    CrawlOne {
+        #[arg(long, value_enum)]
+        store: StoreArg,
+        /// App Store numeric ID or Play Store package name
+        #[arg(long)]
+        id: String,
+        /// Two-letter country code
+        #[arg(long, default_value = "us")]
+        country: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StoreArg {
+    AppStore,
+    PlayStore,
+}
+
+impl StoreArg {
+    /// An empty selection means every store.
+    pub fn is_selected(self, stores: &[StoreArg]) -> bool {
+        stores.is_empty() || stores.contains(&self)
+    }
+
+    pub fn store(self) -> Store {
+        match self {
+            StoreArg::AppStore => Store::AppStore,
+            StoreArg::PlayStore => Store::PlayStore,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_to_crawl_with_default_options() {
+        let cli = Cli::try_parse_from(["app-review-crawler"]).unwrap();
+
+        assert!(cli.command.is_none());
+        assert_eq!(cli.options.config, PathBuf::from("target_apps.json"));
+        assert_eq!(cli.options.output_dir, PathBuf::from("output"));
+        assert_eq!(cli.options.app_store_max_pages, APP_STORE_MAX_PAGES);
+        assert_eq!(cli.options.play_store_max_pages, GOOGLE_PLAY_MAX_PAGES);
+        assert!(!cli.options.fail_fast);
+        assert_eq!(cli.options.log_level, tracing::Level::INFO);
+    }
+
+    #[test]
+    fn test_crawl_one_with_global_flags_after_subcommand() {
+        let cli = Cli::try_parse_from([
+            "app-review-crawler",
+            "crawl-one",
+            "--store",
+            "app-store",
+            "--id",
+            "123",
+            "--country",
+            "kr",
+            "--output-dir",
+            "/tmp/reviews",
+            "--log-level",
+            "debug",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Some(Command::CrawlOne { store, id, country }) => {
+                assert_eq!(store, StoreArg::AppStore);
+                assert_eq!(id, "123");
+                assert_eq!(country, "kr");
+            }
+            other => panic!("Expected crawl-one, got {other:?}"),
+        }
+        assert_eq!(cli.options.output_dir, PathBuf::from("/tmp/reviews"));
+        assert_eq!(cli.options.log_level, tracing::Level::DEBUG);
+    }
+
+    #[test]
+    fn test_store_selection() {
+        let cli =
+            Cli::try_parse_from(["app-review-crawler", "crawl", "--store", "play-store"]).unwrap();
+
+        let Some(Command::Crawl { stores }) = cli.command else {
+            panic!("Expected crawl");
+        };
+        assert!(StoreArg::PlayStore.is_selected(&stores));
+        assert!(!StoreArg::AppStore.is_selected(&stores));
+        assert!(StoreArg::AppStore.is_selected(&[]));
+    }
+
+    #[test]
+    fn test_rate_limit_flags() {
+        let cli = Cli::try_parse_from([
+            "app-review-crawler",
+            "--play-store-rate",
+            "0.5",
+            "--play-store-burst",
+            "1",
+        ])
+        .unwrap();
+
+        let limit = cli.options.play_store_rate_limit();
+        assert_eq!(limit.requests_per_second, 0.5);
+        assert_eq!(limit.burst, 1);
+        assert_eq!(
+            cli.options.app_store_rate_limit().requests_per_second,
+            APP_STORE_RATE_LIMIT.requests_per_second
+        );
+
+        for (flag, value) in [
+            ("--app-store-rate", "0"),
+            ("--app-store-rate", "-2"),
+            ("--app-store-rate", "NaN"),
+            ("--app-store-burst", "0"),
+        ] {
+            assert!(Cli::try_parse_from(["app-review-crawler", flag, value]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_retry_flags() {
+        let cli = Cli::try_parse_from([
+            "app-review-crawler",
+            "--app-store-max-attempts",
+            "2",
+            "--app-store-retry-base-delay",
+            "0.25",
+            "--app-store-retry-max-delay",
+            "10",
+        ])
+        .unwrap();
+
+        let retry = cli.options.app_store_retry();
+        assert_eq!(retry.max_attempts, 2);
+        assert_eq!(retry.base_delay, Duration::from_millis(250));
+        assert_eq!(retry.max_delay, Duration::from_secs(10));
+        assert_eq!(retry.jitter, APP_STORE_RETRY.jitter);
+        // 지정하지 않은 스토어는 기본값 그대로
+        let retry = cli.options.play_store_retry();
+        assert_eq!(retry.max_attempts, GOOGLE_PLAY_RETRY.max_attempts);
+        assert_eq!(retry.base_delay, GOOGLE_PLAY_RETRY.base_delay);
+        assert_eq!(retry.max_delay, GOOGLE_PLAY_RETRY.max_delay);
+
+        for (flag, value) in [
+            ("--play-store-max-attempts", "0"),
+            ("--play-store-retry-base-delay", "-1"),
+            ("--play-store-retry-max-delay", "inf"),
+        ] {
+            assert!(Cli::try_parse_from(["app-review-crawler", flag, value]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_rejects_unknown_store() {
+        let result = Cli::try_parse_from(["app-review-crawler", "crawl", "--store", "amazon"]);
+        assert!(result.is_err());
+    }
+}
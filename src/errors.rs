@@ -1,21 +1,244 @@
 use std::error::Error;
 use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use reqwest::StatusCode;
+
+use crate::review::Store;
+
+pub type BoxError = Box<dyn Error + Send + Sync>;
+
+// 오류 메시지에 포함할 원문 길이
+const SNIPPET_CHARS: usize = 120;
 
 #[derive(Debug)]
 pub enum CrawlerError {
-    ConfigLoad(String),
-    Request(String),
-    Parse(String),
+    /// The store answered with an error status other than 429.
+    Http {
+        status: StatusCode,
+        url: String,
+        source: reqwest::Error,
+    },
+    /// The connection failed or broke off, or the request could not be built.
+    Network { source: reqwest::Error },
+    /// The request timed out.
+    Timeout { source: reqwest::Error },
+    /// The store answered 429 Too Many Requests.
+    RateLimited {
+        retry_after: Option<Duration>,
+        source: reqwest::Error,
+    },
+    /// A response, or a file written by an earlier run, could not be parsed.
+    /// `snippet` is the start of the offending input.
+    Parse {
+        store: Option<Store>,
+        page: Option<u32>,
+        snippet: String,
+        source: BoxError,
+    },
+    /// Reading or writing a local file failed.
+    Io { path: PathBuf, source: io::Error },
+    /// The config file is missing or invalid. `field` names the offending key
+    /// when it is known.
+    Config {
+        field: Option<String>,
+        source: BoxError,
+    },
+    /// A database or output encoder (`sink` names which) rejected a write.
+    Storage {
+        sink: &'static str,
+        source: BoxError,
+    },
+}
+
+impl CrawlerError {
+    /// Maps a reqwest failure without a response to `Timeout` or `Network`.
+    pub fn from_request(source: reqwest::Error) -> Self {
+        if source.is_timeout() {
+            CrawlerError::Timeout { source }
+        } else {
+            CrawlerError::Network { source }
+        }
+    }
+
+    /// Parse error without page context; [`CrawlerError::on_page`] adds it.
+    pub fn parse(store: Option<Store>, input: &[u8], source: impl Into<BoxError>) -> Self {
+        CrawlerError::Parse {
+            store,
+            page: None,
+            snippet: snippet(input),
+            source: source.into(),
+        }
+    }
+
+    pub fn io(path: impl AsRef<Path>, source: io::Error) -> Self {
+        CrawlerError::Io {
+            path: path.as_ref().to_path_buf(),
+            source,
+        }
+    }
+
+    pub fn config(field: Option<&str>, source: impl Into<BoxError>) -> Self {
+        CrawlerError::Config {
+            field: field.map(str::to_string),
+            source: source.into(),
+        }
+    }
+
+    pub fn storage(sink: &'static str, source: impl Into<BoxError>) -> Self {
+        CrawlerError::Storage {
+            sink,
+            source: source.into(),
+        }
+    }
+
+    /// Records the page a parse error came from; other errors are unchanged.
+    pub fn on_page(mut self, page_number: u32) -> Self {
+        if let CrawlerError::Parse { page, .. } = &mut self {
+            page.get_or_insert(page_number);
+        }
+        self
+    }
+
+    /// Whether sending the same request again may succeed: network errors,
+    /// timeouts, 429, 408 and 5xx.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            CrawlerError::Network { source } => !source.is_builder(),
+            CrawlerError::Timeout { .. } | CrawlerError::RateLimited { .. } => true,
+            CrawlerError::Http { status, .. } => {
+                *status == StatusCode::REQUEST_TIMEOUT || status.is_server_error()
+            }
+            _ => false,
+        }
+    }
+
+    /// Short, stable name of the variant for logs, reports and alerts.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            CrawlerError::Http { .. } => "http",
+            CrawlerError::Network { .. } => "network",
+            CrawlerError::Timeout { .. } => "timeout",
+            CrawlerError::RateLimited { .. } => "rate_limited",
+            CrawlerError::Parse { .. } => "parse",
+            CrawlerError::Io { .. } => "io",
+            CrawlerError::Config { .. } => "config",
+            CrawlerError::Storage { .. } => "storage",
+        }
+    }
 }
 
 impl fmt::Display for CrawlerError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            CrawlerError::ConfigLoad(msg) => write!(f, "Config load error: {msg}"),
-            CrawlerError::Request(msg) => write!(f, "Request error: {msg}"),
-            CrawlerError::Parse(msg) => write!(f, "Parse error: {msg}"),
+            CrawlerError::Http { status, url, .. } => write!(f, "HTTP {status} from {url}"),
+            CrawlerError::Network { source } => write!(f, "Network error: {source}"),
+            CrawlerError::Timeout { source } => write!(f, "Request timed out: {source}"),
+            CrawlerError::RateLimited { retry_after, .. } => match retry_after {
+                Some(delay) => write!(f, "Rate limited (retry after {}s)", delay.as_secs()),
+                None => write!(f, "Rate limited"),
+            },
+            CrawlerError::Parse {
+                store,
+                page,
+                snippet,
+                source,
+            } => {
+                write!(f, "Parse error")?;
+                if let Some(store) = store {
+                    write!(f, " in {store} response")?;
+                }
+                if let Some(page) = page {
+                    write!(f, " on page {page}")?;
+                }
+                write!(f, ": {source}")?;
+                if !snippet.is_empty() {
+                    write!(f, " (at {snippet:?})")?;
+                }
+                Ok(())
+            }
+            CrawlerError::Io { path, source } => {
+                write!(f, "I/O error on {}: {source}", path.display())
+            }
+            CrawlerError::Config { field, source } => match field {
+                Some(field) => write!(f, "Config error in `{field}`: {source}"),
+                None => write!(f, "Config error: {source}"),
+            },
+            CrawlerError::Storage { sink, source } => write!(f, "{sink} error: {source}"),
+        }
+    }
+}
+
+impl Error for CrawlerError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CrawlerError::Http { source, .. }
+            | CrawlerError::Network { source }
+            | CrawlerError::Timeout { source }
+            | CrawlerError::RateLimited { source, .. } => Some(source),
+            CrawlerError::Io { source, .. } => Some(source),
+            CrawlerError::Parse { source, .. }
+            | CrawlerError::Config { source, .. }
+            | CrawlerError::Storage { source, .. } => Some(source.as_ref()),
         }
     }
 }
 
-impl Error for CrawlerError {}
+/// The start of `input`, cut at a character boundary.
+fn snippet(input: &[u8]) -> String {
+    let text = String::from_utf8_lossy(input);
+    let text = text.trim();
+    match text.char_indices().nth(SNIPPET_CHARS) {
+        Some((end, _)) => format!("{}...", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_display_and_source() {
+        let source = serde_json::from_str::<serde_json::Value>("{oops").unwrap_err();
+        let error = CrawlerError::parse(Some(Store::PlayStore), b"  {oops", source).on_page(3);
+
+        assert_eq!(error.kind(), "parse");
+        assert!(!error.is_transient());
+        let message = error.to_string();
+        assert!(message.starts_with("Parse error in play_store response on page 3: key must"));
+        assert!(message.ends_with(r#"(at "{oops")"#));
+        assert!(error
+            .source()
+            .unwrap()
+            .downcast_ref::<serde_json::Error>()
+            .is_some());
+
+        // 처음 기록된 페이지 번호를 유지
+        assert!(error.on_page(7).to_string().contains("on page 3"));
+    }
+
+    #[test]
+    fn test_snippet_is_truncated_at_char_boundary() {
+        let long = "가".repeat(SNIPPET_CHARS + 10);
+        let cut = snippet(long.as_bytes());
+        assert_eq!(cut.chars().count(), SNIPPET_CHARS + 3);
+        assert!(cut.ends_with("..."));
+    }
+
+    #[test]
+    fn test_io_error_keeps_path_and_source() {
+        let error = CrawlerError::io(
+            "output/app_store/1.csv",
+            io::Error::new(io::ErrorKind::PermissionDenied, "denied"),
+        );
+        assert_eq!(
+            error.to_string(),
+            "I/O error on output/app_store/1.csv: denied"
+        );
+        let source = error.source().unwrap().downcast_ref::<io::Error>().unwrap();
+        assert_eq!(source.kind(), io::ErrorKind::PermissionDenied);
+    }
+}
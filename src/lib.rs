@@ -0,0 +1,48 @@
+//! Asynchronous crawler for Apple App Store and Google Play Store reviews.
+//!
+//! A [`Crawler`] pages through a store with a [`TBuildRequest`] client and
+//! yields raw page bodies; a [`ResponseProcessor`] runs each page through a
+//! [`TExtractData`] extractor, which maps it into store-agnostic [`Review`]s,
+//! and writes the reviews to every [`TSink`] as they arrive.
+//!
+//! ```no_run
+//! use app_review_crawler::{
+//!     response_processor::app_store::AppStoreExtractor, review_crawler::app_store::AppStoreClient,
+//!     Crawler, CsvSink, ResponseProcessor,
+//! };
+//!
+//! # async fn run() -> Result<(), app_review_crawler::CrawlerError> {
+//! let crawler = Crawler::new(AppStoreClient::new("1194408342", "us"));
+//! let extractor = AppStoreExtractor::new("1194408342", "us");
+//! let sink = CsvSink::append("output/app_store/1194408342.csv")?;
+//! let processor = ResponseProcessor::new(extractor).with_sink(Box::new(sink));
+//! processor.run(crawler.into_pages()).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod errors;
+pub mod report;
+pub mod response_processor;
+pub mod review;
+pub mod review_crawler;
+pub mod runner;
+pub mod target_app;
+
+pub use errors::CrawlerError;
+pub use report::{AppReport, AppStatus, RunReport};
+pub use response_processor::{
+    app_store::AppStoreExtractor,
+    play_store::PlayStoreExtractor,
+    sink::{
+        CsvSink, NdjsonSink, ParquetSink, PostgresDatabase, PostgresSink, PreparedSink, SinkConfig,
+        SqliteSink,
+    },
+    traits::{TExtractData, TSink, TStoreType},
+    PageError, ResponseProcessor, RunStats,
+};
+pub use review::{DeveloperReply, Review, Store};
+pub use review_crawler::{
+    app_store::AppStoreClient, play_store::PlayStoreClient, Crawler, HasAppInfo, RateLimit,
+    RateLimiter, RetryPolicy, TBuildRequest,
+};
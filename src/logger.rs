@@ -1,5 +1,10 @@
 use tracing_subscriber::fmt;
 
 pub fn init(level: tracing::Level) {
-    fmt().with_max_level(level).with_level(true).init();
+    // 로그는 stderr로 보내서 list-apps 같은 명령의 stdout 출력과 섞이지 않게 함
+    fmt()
+        .with_max_level(level)
+        .with_level(true)
+        .with_writer(std::io::stderr)
+        .init();
 }
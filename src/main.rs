@@ -1,115 +1,178 @@
-use tokio::task;
-use tracing::Instrument;
+use std::process::ExitCode;
 
-use crate::{
-    response_processor::{
-        app_store::AppStoreReview, play_store::PlayStoreReview, RawResponse, ResponseProcessor,
-    },
-    review_crawler::{
-        traits::{HasAppInfo, TBuildRequest},
-        Crawler,
-    },
-    target_app::load_target_apps,
+use app_review_crawler::{
+    response_processor::sink::{default_sinks, SinkConfig},
+    review_crawler::{app_store::AppStoreClient, play_store::PlayStoreClient},
+    runner,
+    target_app::{check_app, load_target_apps, Clients},
+    CrawlerError,
 };
+use clap::Parser;
+use tokio::task;
+use tokio_util::sync::CancellationToken;
 
-mod errors;
+use crate::cli::{Cli, Command, Options, StoreArg};
+
+mod cli;
 mod logger;
-mod response_processor;
-mod review_crawler;
-mod target_app;
 
-// * System constants
-const TARGET_APPS_PATH: &str = "target_apps.json";
-const OUTPUT_PATH: &str = "output";
-const APP_STORE_MAX_PAGES: u32 = 10;
-const GOOGLE_PLAY_MAX_PAGES: u32 = 100;
+/// Exit code when the config file cannot be loaded (`EX_CONFIG` from sysexits.h).
+const EX_CONFIG: u8 = 78;
 
-// * Logging level
-const LOG_LEVEL: tracing::Level = tracing::Level::DEBUG;
+/// Resolves on Ctrl-C, or on SIGTERM on Unix.
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
 
-async fn run_store_crawler<C, D, F>(store_name: &str, apps: Vec<C>, make_extractor: F)
-where
-    C: TBuildRequest + HasAppInfo + Clone + Send + 'static,
-    D: response_processor::traits::TExtractData
-        + response_processor::traits::TStoreType
-        + Send
-        + 'static,
-    F: Fn() -> D,
-{
-    tracing::info!("Starting {} crawler task", store_name);
-    tracing::info!("Found {} {} apps to crawl", apps.len(), store_name);
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = terminate.recv() => {}
+                }
+                return;
+            }
+            Err(e) => tracing::warn!("Failed to listen for SIGTERM: {}", e),
+        }
+    }
 
-    for (i, app) in apps.iter().enumerate() {
-        tracing::info!(
-            "Crawling {} app {}/{}: {} (country: {})",
-            store_name,
-            i + 1,
-            apps.len(),
-            app.app_id(),
-            app.country()
-        );
+    if let Err(e) = tokio::signal::ctrl_c().await {
+        tracing::warn!("Failed to listen for Ctrl-C: {}", e);
+        std::future::pending::<()>().await;
+    }
+}
 
-        let app_id = app.app_id().to_string();
-        let mut crawler = Crawler::new(app.clone());
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    crate::logger::init(cli.options.log_level);
+    let options = &cli.options;
 
-        match crawler.run().await {
-            Ok(response) => {
-                tracing::info!("Successfully got response for app: {}", app_id);
-                let processor: ResponseProcessor<D> = ResponseProcessor::new(
-                    RawResponse::new(response),
-                    make_extractor(),
-                    app_id.clone(),
+    match cli.command.unwrap_or(Command::Crawl { stores: Vec::new() }) {
+        Command::Crawl { stores } => {
+            let Some(target_apps) = load_config(options) else {
+                return ExitCode::from(EX_CONFIG);
+            };
+            let app_store_apps = StoreArg::AppStore
+                .is_selected(&stores)
+                .then(|| target_apps.app_store_apps.into_inner());
+            let play_store_apps = StoreArg::PlayStore
+                .is_selected(&stores)
+                .then(|| target_apps.play_store_apps.into_inner());
+            crawl(options, target_apps.sinks, app_store_apps, play_store_apps).await
+        }
+        Command::ValidateConfig => match load_config(options) {
+            Some(target_apps) => {
+                println!(
+                    "{} is valid: {} App Store apps, {} Play Store apps",
+                    options.config.display(),
+                    target_apps.app_store_apps.read().await.len(),
+                    target_apps.play_store_apps.read().await.len()
                 );
-
-                match processor.run().await {
-                    Ok(_) => tracing::info!(
-                        "Successfully processed and saved reviews for app: {}",
-                        app_id
-                    ),
-                    Err(e) => {
-                        tracing::error!("Failed to process reviews for app {}: {}", app_id, e)
-                    }
+                ExitCode::SUCCESS
+            }
+            None => ExitCode::from(EX_CONFIG),
+        },
+        Command::ListApps { stores } => {
+            let Some(target_apps) = load_config(options) else {
+                return ExitCode::from(EX_CONFIG);
+            };
+            if StoreArg::AppStore.is_selected(&stores) {
+                for app in target_apps.app_store_apps.read().await.iter() {
+                    println!("app-store\t{}\t{}", app.app_id, app.country);
+                }
+            }
+            if StoreArg::PlayStore.is_selected(&stores) {
+                for app in target_apps.play_store_apps.read().await.iter() {
+                    println!("play-store\t{}\t{}", app.app_id, app.country);
                 }
             }
-            Err(e) => {
-                tracing::error!("Failed to crawl app {}: {}", app_id, e);
+            ExitCode::SUCCESS
+        }
+        Command::CrawlOne { store, id, country } => {
+            // 설정 파일과 같은 규칙으로 검사해 잘못된 키로 파일을 쓰지 않도록 함
+            if let Err(e) = check_app(store.store(), &id, &country) {
+                tracing::error!("Invalid app to crawl: {}", e);
+                return ExitCode::from(EX_CONFIG);
+            }
+            // 설정 파일이 있으면 그 sinks에 쓰고, 없으면 CSV만 씀
+            let sinks = if options.config.exists() {
+                let Some(target_apps) = load_config(options) else {
+                    return ExitCode::from(EX_CONFIG);
+                };
+                target_apps.sinks
+            } else {
+                tracing::info!(
+                    "No config file at {}, writing CSV only",
+                    options.config.display()
+                );
+                default_sinks()
+            };
+            match store {
+                StoreArg::AppStore => {
+                    let apps = vec![AppStoreClient::new(id, country)];
+                    crawl(options, sinks, Some(apps), None).await
+                }
+                StoreArg::PlayStore => {
+                    let apps = vec![PlayStoreClient::new(id, country)];
+                    crawl(options, sinks, None, Some(apps)).await
+                }
             }
         }
     }
 }
 
-#[tokio::main]
-async fn main() {
-    crate::logger::init(LOG_LEVEL);
-    tracing::info!("Starting app review crawler...");
-    let target_apps = match load_target_apps(TARGET_APPS_PATH) {
+fn load_config(options: &Options) -> Option<Clients> {
+    match load_target_apps(&options.config) {
         Ok(apps) => {
             tracing::info!("Successfully loaded target apps");
-            apps
+            Some(apps)
         }
         Err(e) => {
-            tracing::error!("Failed to load target apps: {}", e);
-            return;
+            tracing::error!(
+                "Failed to load target apps from {}: {}",
+                options.config.display(),
+                e
+            );
+            None
         }
-    };
+    }
+}
 
-    task::spawn(
-        async move {
-            let apps = target_apps.app_store_apps.read().await.clone();
-            run_store_crawler("App Store", apps, AppStoreReview::new).await;
-        }
-        .instrument(tracing::info_span!("App Store crawler")),
-    );
+/// Crawls the given apps (`None` skips a store) into `sinks` and maps the
+/// outcome to an exit code.
+async fn crawl(
+    options: &Options,
+    sinks: Vec<SinkConfig>,
+    app_store_apps: Option<Vec<AppStoreClient>>,
+    play_store_apps: Option<Vec<PlayStoreClient>>,
+) -> ExitCode {
+    // 종료 신호가 오면 진행 중인 앱은 중단하고, 이미 끝난 앱의 결과는 그대로 둠
+    let shutdown = CancellationToken::new();
+    let signal_shutdown = shutdown.clone();
+    let signal_task = task::spawn(async move {
+        shutdown_signal().await;
+        tracing::warn!("Shutdown requested, finishing up...");
+        signal_shutdown.cancel();
+    });
 
-    task::spawn(
-        async move {
-            let apps = target_apps.play_store_apps.read().await.clone();
-            run_store_crawler("Play Store", apps, PlayStoreReview::new).await;
-        }
-        .instrument(tracing::info_span!("Play Store crawler")),
-    );
+    let settings = options.run_settings(sinks);
+    let result = runner::crawl(&settings, app_store_apps, play_store_apps, shutdown.clone()).await;
+    signal_task.abort();
 
-    // Sleep to keep the main thread alive
-    tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
-    tracing::info!("Crawler finished");
+    match result {
+        Ok(outcome) => {
+            print!("{}", outcome.report);
+            if outcome.any_failed {
+                ExitCode::FAILURE
+            } else if shutdown.is_cancelled() {
+                ExitCode::from(130)
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+        Err(CrawlerError::Config { .. }) => ExitCode::from(EX_CONFIG),
+        Err(_) => ExitCode::FAILURE,
+    }
 }
@@ -0,0 +1,289 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::{
+    errors::CrawlerError,
+    response_processor::{atomic::write_atomic, PageError, RunStats},
+    review::Store,
+};
+
+/// How the crawl of one app ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AppStatus {
+    Succeeded,
+    /// Finished, but some pages could not be fetched or parsed.
+    Degraded,
+    Failed,
+    Cancelled,
+}
+
+impl AppStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AppStatus::Succeeded => "succeeded",
+            AppStatus::Degraded => "degraded",
+            AppStatus::Failed => "failed",
+            AppStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+/// An error as it appears in the report.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ErrorReport {
+    /// [`CrawlerError::kind`]
+    pub kind: &'static str,
+    /// The page the error happened on, when it is known.
+    pub page: Option<u32>,
+    pub message: String,
+}
+
+impl From<&CrawlerError> for ErrorReport {
+    fn from(error: &CrawlerError) -> Self {
+        let page = match error {
+            CrawlerError::Parse { page, .. } => *page,
+            _ => None,
+        };
+        Self {
+            kind: error.kind(),
+            page,
+            message: error.to_string(),
+        }
+    }
+}
+
+impl From<&PageError> for ErrorReport {
+    fn from(failed: &PageError) -> Self {
+        Self {
+            page: Some(failed.page),
+            ..Self::from(&failed.error)
+        }
+    }
+}
+
+/// Result of crawling one app in one country.
+#[derive(Debug, Clone, Serialize)]
+pub struct AppReport {
+    pub store: Store,
+    pub app_id: String,
+    pub country: String,
+    pub status: AppStatus,
+    #[serde(flatten)]
+    pub stats: RunStats,
+    pub errors: Vec<ErrorReport>,
+    #[serde(rename = "duration_ms", serialize_with = "serialize_millis")]
+    pub duration: Duration,
+}
+
+/// Everything one run of the crawler did, app by app in config order.
+#[derive(Debug, Clone, Serialize)]
+pub struct RunReport {
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub apps: Vec<AppReport>,
+}
+
+impl RunReport {
+    /// `<output_dir>/reports/run_<started_at>.json`
+    pub fn path(&self, output_dir: &Path) -> PathBuf {
+        output_dir.join("reports").join(format!(
+            "run_{}.json",
+            self.started_at.format("%Y%m%dT%H%M%SZ")
+        ))
+    }
+
+    /// Writes the report as JSON and returns its path.
+    pub fn write(&self, output_dir: &Path) -> Result<PathBuf, CrawlerError> {
+        let path = self.path(output_dir);
+        let json =
+            serde_json::to_vec_pretty(self).map_err(|e| CrawlerError::storage("run report", e))?;
+        write_atomic(&path, &json)?;
+        Ok(path)
+    }
+}
+
+/// Renders the report as a plain-text table, one row per app.
+impl fmt::Display for RunReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let app_width = self
+            .apps
+            .iter()
+            .map(|app| app.app_id.len())
+            .chain(["APP".len()])
+            .max()
+            .unwrap_or_default();
+
+        writeln!(
+            f,
+            "{:<10}  {:<app_width$}  {:<7}  {:<9}  {:>5}  {:>6}  {:>5}  {:>7}  {:>6}  {:>8}",
+            "STORE",
+            "APP",
+            "COUNTRY",
+            "STATUS",
+            "PAGES",
+            "PARSED",
+            "NEW",
+            "SKIPPED",
+            "ERRORS",
+            "DURATION"
+        )?;
+        for app in &self.apps {
+            writeln!(
+                f,
+                "{:<10}  {:<app_width$}  {:<7}  {:<9}  {:>5}  {:>6}  {:>5}  {:>7}  {:>6}  {:>7.1}s",
+                app.store.as_str(),
+                app.app_id,
+                app.country,
+                app.status.as_str(),
+                app.stats.pages,
+                app.stats.reviews_parsed,
+                app.stats.new_reviews,
+                app.stats.skipped_entries,
+                app.errors.len(),
+                app.duration.as_secs_f64()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn serialize_millis<S: serde::Serializer>(
+    duration: &Duration,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(duration.as_millis() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn report() -> RunReport {
+        let started_at = Utc.with_ymd_and_hms(2026, 10, 17, 9, 30, 0).unwrap();
+        let error = CrawlerError::parse(Some(Store::PlayStore), b"{oops", "bad payload").on_page(2);
+        RunReport {
+            started_at,
+            finished_at: started_at + chrono::Duration::seconds(12),
+            apps: vec![
+                AppReport {
+                    store: Store::AppStore,
+                    app_id: "1194408342".to_string(),
+                    country: "us".to_string(),
+                    status: AppStatus::Succeeded,
+                    stats: RunStats {
+                        pages: 3,
+                        reviews_parsed: 150,
+                        skipped_entries: 1,
+                        new_reviews: 12,
+                    },
+                    errors: Vec::new(),
+                    duration: Duration::from_millis(4_210),
+                },
+                AppReport {
+                    store: Store::PlayStore,
+                    app_id: "com.whatsapp".to_string(),
+                    country: "kr".to_string(),
+                    status: AppStatus::Failed,
+                    stats: RunStats {
+                        pages: 2,
+                        reviews_parsed: 100,
+                        skipped_entries: 0,
+                        new_reviews: 100,
+                    },
+                    errors: vec![ErrorReport::from(&error)],
+                    duration: Duration::from_millis(940),
+                },
+                AppReport {
+                    store: Store::AppStore,
+                    app_id: "1194408342".to_string(),
+                    country: "kr".to_string(),
+                    status: AppStatus::Degraded,
+                    stats: RunStats {
+                        pages: 9,
+                        reviews_parsed: 450,
+                        skipped_entries: 0,
+                        new_reviews: 0,
+                    },
+                    errors: vec![ErrorReport::from(&PageError {
+                        page: 4,
+                        error: CrawlerError::parse(Some(Store::AppStore), b"<feed", "truncated"),
+                    })],
+                    duration: Duration::from_millis(12_080),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_report_json() {
+        let json = serde_json::to_value(report()).unwrap();
+
+        assert_eq!(json["started_at"], "2026-10-17T09:30:00Z");
+        let app = &json["apps"][1];
+        assert_eq!(app["store"], "play_store");
+        assert_eq!(app["app_id"], "com.whatsapp");
+        assert_eq!(app["country"], "kr");
+        assert_eq!(app["status"], "failed");
+        assert_eq!(app["pages"], 2);
+        assert_eq!(app["reviews_parsed"], 100);
+        assert_eq!(app["new_reviews"], 100);
+        assert_eq!(app["skipped_entries"], 0);
+        assert_eq!(app["duration_ms"], 940);
+        assert_eq!(app["errors"][0]["kind"], "parse");
+        assert_eq!(app["errors"][0]["page"], 2);
+        assert!(app["errors"][0]["message"]
+            .as_str()
+            .unwrap()
+            .contains("on page 2"));
+
+        // 건너뛴 페이지는 오류에 페이지 번호가 붙음
+        let app = &json["apps"][2];
+        assert_eq!(app["status"], "degraded");
+        assert_eq!(app["errors"][0]["page"], 4);
+        assert!(json["apps"][0]["errors"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_report_table() {
+        let table = report().to_string();
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[0],
+            "STORE       APP           COUNTRY  STATUS     PAGES  PARSED    NEW  SKIPPED  ERRORS  DURATION"
+        );
+        assert_eq!(
+            lines[1],
+            "app_store   1194408342    us       succeeded      3     150     12        1       0      4.2s"
+        );
+        assert_eq!(
+            lines[2],
+            "play_store  com.whatsapp  kr       failed         2     100    100        0       1      0.9s"
+        );
+        assert_eq!(
+            lines[3],
+            "app_store   1194408342    kr       degraded       9     450      0        0       1     12.1s"
+        );
+    }
+
+    #[test]
+    fn test_write_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = report().write(dir.path()).unwrap();
+
+        assert_eq!(path, dir.path().join("reports/run_20261017T093000Z.json"));
+        let json: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(json["apps"].as_array().unwrap().len(), 3);
+    }
+}
@@ -1,84 +1,128 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
 use quick_xml::events::attributes::Attribute;
 use quick_xml::events::Event;
 use quick_xml::name::QName;
 use quick_xml::Reader;
-use serde::{Deserialize, Serialize};
 
 use crate::{
     errors::CrawlerError,
-    response_processor::traits::{TExtractData, TStoreType},
-    OUTPUT_PATH,
+    response_processor::traits::{ExtractedPage, TExtractData, TStoreType},
+    review::{Review, Store},
 };
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AppStoreReview {
-    pub date: String,
-    pub star: i32,
-    pub like: i32,
-    pub dislike: i32,
-    pub title: String,
-    pub review: String,
+/// Maps App Store customer review RSS pages into [`Review`]s.
+#[derive(Debug, Clone)]
+pub struct AppStoreExtractor {
+    app_id: String,
+    country: String,
 }
 
-impl AppStoreReview {
-    pub fn new() -> Self {
+impl AppStoreExtractor {
+    pub fn new(app_id: impl Into<String>, country: impl Into<String>) -> Self {
         Self {
-            date: String::new(),
-            star: 0,
-            like: 0,
-            dislike: 0,
-            title: String::new(),
-            review: String::new(),
+            app_id: app_id.into(),
+            country: country.into(),
         }
     }
 }
 
-impl TStoreType for AppStoreReview {
-    fn get_output_path(&self, app_id: &str) -> String {
-        format!("{OUTPUT_PATH}/app_store/{app_id}.csv")
+// <entry> 하나를 읽는 동안 필드를 담아두는 임시 구조체
+#[derive(Debug, Default)]
+struct Entry {
+    review_id: String,
+    date: String,
+    star: u8,
+    vote_sum: u32,
+    vote_count: Option<u32>,
+    title: String,
+    review: String,
+    version: String,
+    author: String,
+    author_uri: String,
+}
+
+impl TStoreType for AppStoreExtractor {
+    fn store(&self) -> Store {
+        Store::AppStore
     }
 }
 
-impl TExtractData for AppStoreReview {
-    fn extract_data(&self, response: &[u8]) -> Result<Vec<Self>, CrawlerError> {
+impl TExtractData for AppStoreExtractor {
+    fn extract_data(&self, response: &[u8]) -> Result<Vec<Review>, CrawlerError> {
+        self.extract_page(response).map(|page| page.reviews)
+    }
+
+    fn extract_page(&self, response: &[u8]) -> Result<ExtractedPage, CrawlerError> {
         tracing::debug!("Starting XML parsing with quick-xml");
 
         let mut reader = Reader::from_reader(response);
         reader.trim_text(true);
 
         let mut buf: Vec<u8> = Vec::new();
-        let mut reviews = Vec::new();
+        let mut page = ExtractedPage::default();
 
         // 임시로 필드를 담을 변수들
-        let mut current = AppStoreReview::new();
+        let mut current = Entry::default();
+        let mut language = None;
         let mut in_entry = false;
+        let mut in_author = false;
 
         loop {
             match reader.read_event() {
+                Ok(Event::Start(ref e)) if e.name() == QName(b"feed") => {
+                    // 피드 전체의 언어 (xml:lang)
+                    language = e
+                        .attributes()
+                        .filter_map(Result::ok)
+                        .find(|attr| attr.key == QName(b"xml:lang"))
+                        .map(|attr| String::from_utf8_lossy(&attr.value).into_owned());
+                }
+
                 Ok(Event::Start(ref e)) => {
-                    Self::handle_start_event(e, &mut reader, &mut current, &mut in_entry);
+                    Self::handle_start_event(
+                        e,
+                        &mut reader,
+                        &mut current,
+                        &mut in_entry,
+                        &mut in_author,
+                    );
+                }
+
+                Ok(Event::End(ref e)) if e.name() == QName(b"author") => {
+                    in_author = false;
                 }
 
                 Ok(Event::End(ref e)) if e.name() == QName(b"entry") => {
                     // push the completed review
                     tracing::debug!("Exit </entry>: {:?}", current);
-                    // if the required fields (title, review) are present, push
-                    if !current.title.is_empty() && !current.review.is_empty() {
-                        reviews.push(current.clone());
-                    } else {
-                        tracing::debug!("Skipped incomplete entry");
+                    let entry = std::mem::take(&mut current);
+                    match self.to_review(entry, language.as_deref())? {
+                        Some(review) => page.reviews.push(review),
+                        None => page.skipped += 1,
                     }
                     in_entry = false;
                 }
 
                 Ok(Event::Eof) => {
-                    tracing::debug!("XML parsing completed. Found {} reviews", reviews.len());
+                    tracing::debug!(
+                        "XML parsing completed. Found {} reviews ({} skipped)",
+                        page.reviews.len(),
+                        page.skipped
+                    );
                     break;
                 }
 
                 Err(e) => {
                     tracing::error!("XML parsing error: {}", e);
-                    return Err(CrawlerError::Parse(e.to_string()));
+                    // 오류가 난 위치부터 원문 일부를 함께 남김
+                    let position = reader.buffer_position().min(response.len());
+                    return Err(CrawlerError::parse(
+                        Some(Store::AppStore),
+                        &response[position..],
+                        e,
+                    ));
                 }
 
                 _ => {}
@@ -86,21 +130,61 @@ impl TExtractData for AppStoreReview {
             buf.clear();
         }
 
-        Ok(reviews)
+        Ok(page)
     }
 }
 
-impl AppStoreReview {
+impl AppStoreExtractor {
+    /// Returns `Ok(None)` for entries missing a required field and an error
+    /// for entries whose date cannot be parsed.
+    fn to_review(
+        &self,
+        entry: Entry,
+        language: Option<&str>,
+    ) -> Result<Option<Review>, CrawlerError> {
+        // if the required fields (id, title, review, date) are present, push.
+        // ID가 없으면 모든 sink와 상태 파일의 중복 제거 키가 겹치므로 건너뜀
+        if entry.review_id.trim().is_empty()
+            || entry.title.is_empty()
+            || entry.review.is_empty()
+            || entry.date.is_empty()
+        {
+            tracing::debug!("Skipped incomplete entry");
+            return Ok(None);
+        }
+        let date = parse_date(&entry.date, &entry.review_id)?;
+
+        Ok(Some(Review {
+            store: Store::AppStore,
+            app_id: self.app_id.clone(),
+            country: self.country.clone(),
+            review_id: entry.review_id,
+            author: entry.author,
+            author_uri: non_empty(entry.author_uri),
+            date,
+            rating: entry.star,
+            helpful_votes: entry.vote_sum,
+            total_votes: entry.vote_count,
+            version: non_empty(entry.version),
+            language: language.map(str::to_string),
+            title: Some(entry.title),
+            body: entry.review,
+            // RSS 피드에는 개발자 답변이 포함되지 않음
+            developer_reply: None,
+        }))
+    }
+
     fn handle_start_event(
         e: &quick_xml::events::BytesStart,
         reader: &mut Reader<&[u8]>,
-        current: &mut AppStoreReview,
+        current: &mut Entry,
         in_entry: &mut bool,
+        in_author: &mut bool,
     ) {
         match e.name() {
             QName(b"entry") => {
                 *in_entry = true;
-                *current = AppStoreReview::new();
+                *current = Entry::default();
                 tracing::debug!("Enter <entry>");
             }
             QName(b"title") if *in_entry => {
@@ -113,14 +197,32 @@ impl AppStoreReview {
                 Self::read_numeric_field(reader, e.name(), &mut current.star);
             }
             QName(b"im:voteSum") if *in_entry => {
-                Self::read_numeric_field(reader, e.name(), &mut current.like);
+                Self::read_numeric_field(reader, e.name(), &mut current.vote_sum);
             }
             QName(b"im:voteCount") if *in_entry => {
-                Self::handle_vote_count(reader, e.name(), current);
+                let mut total = 0;
+                Self::read_numeric_field(reader, e.name(), &mut total);
+                current.vote_count = Some(total);
             }
             QName(b"updated") if *in_entry => {
                 Self::read_text_field(reader, e.name(), &mut current.date);
             }
+            QName(b"id") if *in_entry => {
+                Self::read_text_field(reader, e.name(), &mut current.review_id);
+            }
+            QName(b"im:version") if *in_entry => {
+                Self::read_text_field(reader, e.name(), &mut current.version);
+            }
+            QName(b"author") if *in_entry => {
+                *in_author = true;
+            }
+            // <author> 안의 <name>, <uri>만 리뷰 작성자 정보
+            QName(b"name") if *in_author => {
+                Self::read_text_field(reader, e.name(), &mut current.author);
+            }
+            QName(b"uri") if *in_author => {
+                Self::read_text_field(reader, e.name(), &mut current.author_uri);
+            }
             _ => {}
         }
     }
@@ -131,16 +233,20 @@ impl AppStoreReview {
         }
     }
 
-    fn read_numeric_field(reader: &mut Reader<&[u8]>, name: QName, field: &mut i32) {
+    fn read_numeric_field<T: FromStr + Default>(
+        reader: &mut Reader<&[u8]>,
+        name: QName,
+        field: &mut T,
+    ) {
         if let Ok(txt) = reader.read_text(name) {
-            *field = txt.parse().unwrap_or(0);
+            *field = txt.parse().unwrap_or_default();
         }
     }
 
     fn handle_content_element(
         reader: &mut Reader<&[u8]>,
         e: &quick_xml::events::BytesStart,
-        current: &mut AppStoreReview,
+        current: &mut Entry,
     ) {
         // type="text"인 content만
         if let Some(Attribute { key: _, value: _ }) = e
@@ -151,18 +257,33 @@ impl AppStoreReview {
             Self::read_text_field(reader, e.name(), &mut current.review);
         }
     }
+}
 
-    fn handle_vote_count(reader: &mut Reader<&[u8]>, name: QName, current: &mut AppStoreReview) {
-        if let Ok(txt) = reader.read_text(name) {
-            let total: i32 = txt.parse().unwrap_or(0);
-            current.dislike = total.saturating_sub(current.like);
-        }
-    }
+/// Parses an RFC 3339 `<updated>` value such as `2025-05-11T10:19:38-07:00` into UTC.
+fn parse_date(date: &str, review_id: &str) -> Result<DateTime<Utc>, CrawlerError> {
+    DateTime::parse_from_rfc3339(date)
+        .map(|date| date.with_timezone(&Utc))
+        .map_err(|e| {
+            CrawlerError::parse(
+                Some(Store::AppStore),
+                date.as_bytes(),
+                format!("Invalid date in review {review_id}: {e}"),
+            )
+        })
+}
+
+fn non_empty(value: String) -> Option<String> {
+    (!value.is_empty()).then_some(value)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    fn extractor() -> AppStoreExtractor {
+        AppStoreExtractor::new("1194408342", "us")
+    }
+
     #[test]
     fn test_extract_app_store_reviews() {
         let xml_content = r#"
@@ -203,7 +324,7 @@ mod tests {
         </feed>
         "#;
 
-        let extractor = AppStoreReview::new();
+        let extractor = extractor();
         let result = extractor.extract_data(xml_content.as_bytes());
 
         assert!(result.is_ok());
@@ -212,20 +333,203 @@ mod tests {
         assert_eq!(reviews.len(), 2);
 
         let first = &reviews[0];
-        assert_eq!(first.title, "Great idea but not well executed.");
-        assert_eq!(first.star, 2);
-        assert_eq!(first.like, 0);
-        assert_eq!(first.dislike, 0);
-        assert_eq!(first.date, "2025-05-11T10:19:38-07:00");
-        assert!(first.review.contains("If you are test, this isn't it"));
+        assert_eq!(first.store, Store::AppStore);
+        assert_eq!(first.app_id, "1194408342");
+        assert_eq!(first.country, "us");
+        assert_eq!(
+            first.title.as_deref(),
+            Some("Great idea but not well executed.")
+        );
+        assert_eq!(first.rating, 2);
+        assert_eq!(first.helpful_votes, 0);
+        assert_eq!(first.total_votes, Some(0));
+        assert_eq!(first.date.to_rfc3339(), "2025-05-11T17:19:38+00:00");
+        assert!(first.body.contains("If you are test, this isn't it"));
+        assert_eq!(first.review_id, "12645174720");
+        assert_eq!(first.version.as_deref(), Some("7.2.3"));
+        assert_eq!(first.language.as_deref(), Some("en"));
+        assert_eq!(first.author, "Beegirl200073?4!/9");
+        assert_eq!(
+            first.author_uri.as_deref(),
+            Some("https://itunes.apple.com/us/reviews/id167338708")
+        );
+        assert!(first.developer_reply.is_none());
 
         let second = &reviews[1];
-        assert_eq!(second.title, "Love it!!");
-        assert_eq!(second.star, 4);
-        assert_eq!(second.like, 0);
-        assert_eq!(second.dislike, 0);
-        assert_eq!(second.date, "2025-03-30T15:13:14-07:00");
-        assert!(second.review.contains("Super helpful and cute!"));
+        assert_eq!(second.title.as_deref(), Some("Love it!!"));
+        assert_eq!(second.rating, 4);
+        assert_eq!(second.date.to_rfc3339(), "2025-03-30T22:13:14+00:00");
+        assert!(second.body.contains("Super helpful and cute!"));
+        assert_eq!(second.review_id, "12484337193");
+        assert_eq!(second.version.as_deref(), Some("7.2.2"));
+        assert_eq!(second.author, "LenaM720");
+        assert_eq!(
+            second.author_uri.as_deref(),
+            Some("https://itunes.apple.com/us/reviews/id108277834")
+        );
+    }
+
+    #[test]
+    fn test_feed_level_author_and_id_are_ignored() {
+        let xml_content = r#"
+        <feed xmlns:im="http://itunes.apple.com/rss" xmlns="http://www.w3.org/2005/Atom">
+            <id>https://itunes.apple.com/us/rss/customerreviews/id=1194408342/sortby=mostrecent/xml</id>
+            <author>
+                <name>iTunes Store</name>
+                <uri>http://www.apple.com/us/itunes/</uri>
+            </author>
+            <entry>
+                <id>1</id>
+                <title>Title</title>
+                <content type="text">Body</content>
+                <im:rating>5</im:rating>
+                <updated>2025-05-11T10:19:38-07:00</updated>
+            </entry>
+        </feed>
+        "#;
+
+        let reviews = extractor().extract_data(xml_content.as_bytes()).unwrap();
+
+        assert_eq!(reviews.len(), 1);
+        assert_eq!(reviews[0].review_id, "1");
+        // 작성자 정보가 없는 항목은 빈 값으로 남음
+        assert!(reviews[0].author.is_empty());
+        assert!(reviews[0].author_uri.is_none());
+        assert!(reviews[0].language.is_none());
+    }
+
+    #[test]
+    fn test_votes_map_to_helpful_and_total() {
+        let xml_content = r#"
+        <feed xmlns:im="http://itunes.apple.com/rss" xmlns="http://www.w3.org/2005/Atom">
+            <entry>
+                <id>1</id>
+                <title>Title</title>
+                <content type="text">Body</content>
+                <im:voteSum>3</im:voteSum>
+                <im:voteCount>5</im:voteCount>
+                <im:rating>4</im:rating>
+                <updated>2025-05-11T10:19:38-07:00</updated>
+            </entry>
+        </feed>
+        "#;
+
+        let reviews = extractor().extract_data(xml_content.as_bytes()).unwrap();
+
+        assert_eq!(reviews.len(), 1);
+        assert_eq!(reviews[0].helpful_votes, 3);
+        assert_eq!(reviews[0].total_votes, Some(5));
+    }
+
+    #[test]
+    fn test_dates_are_normalized_to_utc() {
+        let xml_content = r#"
+        <feed xmlns:im="http://itunes.apple.com/rss" xmlns="http://www.w3.org/2005/Atom">
+            <entry>
+                <id>1</id>
+                <title>Title</title>
+                <content type="text">Body</content>
+                <updated>2025-12-31T23:30:00-07:00</updated>
+            </entry>
+            <entry>
+                <id>2</id>
+                <title>No date</title>
+                <content type="text">Body</content>
+            </entry>
+        </feed>
+        "#;
+
+        let reviews = extractor().extract_data(xml_content.as_bytes()).unwrap();
+
+        // 날짜가 없는 항목은 불완전한 항목으로 건너뜀
+        assert_eq!(reviews.len(), 1);
+        assert_eq!(reviews[0].date.to_rfc3339(), "2026-01-01T06:30:00+00:00");
+        assert_eq!(
+            serde_json::to_value(&reviews[0]).unwrap()["date"],
+            "2026-01-01T06:30:00Z"
+        );
+    }
+
+    #[test]
+    fn test_malformed_date_is_a_parse_error() {
+        let xml_content = r#"
+        <feed xmlns:im="http://itunes.apple.com/rss" xmlns="http://www.w3.org/2005/Atom">
+            <entry>
+                <id>12645174720</id>
+                <title>Title</title>
+                <content type="text">Body</content>
+                <updated>yesterday</updated>
+            </entry>
+        </feed>
+        "#;
+
+        let result = extractor().extract_data(xml_content.as_bytes());
+
+        match result {
+            Err(error @ CrawlerError::Parse { .. }) => {
+                let CrawlerError::Parse { store, snippet, .. } = &error else {
+                    unreachable!()
+                };
+                assert_eq!(*store, Some(Store::AppStore));
+                assert_eq!(snippet, "yesterday");
+                assert!(error.to_string().contains("12645174720"));
+            }
+            other => panic!("Expected a parse error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_incomplete_entries_are_counted_as_skipped() {
+        let xml_content = r#"
+        <feed xmlns:im="http://itunes.apple.com/rss" xmlns="http://www.w3.org/2005/Atom">
+            <entry>
+                <id>1</id>
+                <title>No body</title>
+                <updated>2025-05-11T10:19:38-07:00</updated>
+            </entry>
+            <entry>
+                <id>2</id>
+                <title>Complete</title>
+                <content type="text">Body</content>
+                <updated>2025-05-11T10:19:38-07:00</updated>
+            </entry>
+        </feed>
+        "#;
+
+        let page = extractor().extract_page(xml_content.as_bytes()).unwrap();
+        assert_eq!(page.reviews.len(), 1);
+        assert_eq!(page.reviews[0].review_id, "2");
+        assert_eq!(page.skipped, 1);
+    }
+
+    #[test]
+    fn test_entries_without_id_are_counted_as_skipped() {
+        let xml_content = r#"
+        <feed xmlns:im="http://itunes.apple.com/rss" xmlns="http://www.w3.org/2005/Atom">
+            <entry>
+                <title>No ID</title>
+                <content type="text">Body</content>
+                <updated>2025-05-11T10:19:38-07:00</updated>
+            </entry>
+            <entry>
+                <id>  </id>
+                <title>Blank ID</title>
+                <content type="text">Body</content>
+                <updated>2025-05-11T10:19:38-07:00</updated>
+            </entry>
+            <entry>
+                <id>3</id>
+                <title>Complete</title>
+                <content type="text">Body</content>
+                <updated>2025-05-11T10:19:38-07:00</updated>
+            </entry>
+        </feed>
+        "#;
+
+        let page = extractor().extract_page(xml_content.as_bytes()).unwrap();
+        assert_eq!(page.reviews.len(), 1);
+        assert_eq!(page.reviews[0].review_id, "3");
+        assert_eq!(page.skipped, 2);
     }
 
     #[test]
@@ -238,7 +542,7 @@ mod tests {
         </feed>
         "#;
 
-        let extractor = AppStoreReview::new();
+        let extractor = extractor();
         let result = extractor.extract_data(xml_content.as_bytes()).unwrap();
         assert!(result.is_empty());
     }
@@ -246,7 +550,7 @@ mod tests {
     #[test]
     fn test_extract_data_with_invalid_xml() {
         let invalid_bytes = vec![0xFF, 0xFE, 0x00, 0x00];
-        let extractor = AppStoreReview::new();
+        let extractor = extractor();
         let result = extractor.extract_data(&invalid_bytes);
         // quick-xml handles invalid input gracefully and returns empty result
         assert!(result.is_ok());
@@ -0,0 +1,157 @@
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::errors::CrawlerError;
+
+// 같은 프로세스 안에서 임시 파일 이름이 겹치지 않도록 붙이는 번호
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// A file that is written under a temporary name in the target's directory
+/// and only renamed over the target by [`AtomicFile::commit`], so readers see
+/// either the previous file or the complete new one. Dropping it without
+/// committing removes the temporary file and leaves the target untouched.
+#[derive(Debug)]
+pub struct AtomicFile {
+    file: File,
+    temp_path: PathBuf,
+    path: PathBuf,
+    committed: bool,
+}
+
+impl AtomicFile {
+    /// Creates the temporary file (and the target's directory if needed).
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, CrawlerError> {
+        let path = path.as_ref().to_path_buf();
+        let parent = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        fs::create_dir_all(&parent).map_err(|e| CrawlerError::io(&parent, e))?;
+
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let temp_path = parent.join(format!(
+            ".{file_name}.{}.{}.tmp",
+            std::process::id(),
+            TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let file = File::create(&temp_path).map_err(|e| CrawlerError::io(&temp_path, e))?;
+
+        Ok(Self {
+            file,
+            temp_path,
+            path,
+            committed: false,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Flushes and fsyncs the temporary file, then renames it over the target.
+    pub fn commit(mut self) -> Result<(), CrawlerError> {
+        let path = self.path.clone();
+        let io_error = |e: io::Error| CrawlerError::io(&path, e);
+
+        self.file.flush().map_err(io_error)?;
+        self.file.sync_all().map_err(io_error)?;
+        fs::rename(&self.temp_path, &self.path).map_err(io_error)?;
+        self.committed = true;
+
+        // rename 자체도 디스크에 남도록 디렉토리를 fsync
+        #[cfg(unix)]
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            File::open(parent)
+                .and_then(|dir| dir.sync_all())
+                .map_err(io_error)?;
+        }
+        Ok(())
+    }
+}
+
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        if !self.committed {
+            let _ = fs::remove_file(&self.temp_path);
+        }
+    }
+}
+
+/// Replaces `path` with `contents` atomically.
+pub fn write_atomic(path: impl AsRef<Path>, contents: &[u8]) -> Result<(), CrawlerError> {
+    let mut file = AtomicFile::create(path)?;
+    file.write_all(contents)
+        .map_err(|e| CrawlerError::io(file.path(), e))?;
+    file.commit()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_commit_replaces_target() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.csv");
+        fs::write(&path, "old").unwrap();
+
+        let mut file = AtomicFile::create(&path).unwrap();
+        file.write_all(b"new").unwrap();
+        // 커밋 전에는 이전 파일이 그대로 보임
+        assert_eq!(fs::read_to_string(&path).unwrap(), "old");
+
+        file.commit().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(entries(dir.path()), ["out.csv"]);
+    }
+
+    #[test]
+    fn test_drop_without_commit_keeps_target() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.csv");
+        fs::write(&path, "old").unwrap();
+
+        let mut file = AtomicFile::create(&path).unwrap();
+        file.write_all(b"partial").unwrap();
+        drop(file);
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "old");
+        assert_eq!(entries(dir.path()), ["out.csv"]);
+    }
+
+    #[test]
+    fn test_write_atomic_creates_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a/b/state.json");
+
+        write_atomic(&path, b"{}").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{}");
+    }
+}
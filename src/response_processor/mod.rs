@@ -1,64 +1,614 @@
-use std::marker::PhantomData;
+use std::path::PathBuf;
 
-use reqwest::Response;
+use bytes::Bytes;
+use futures::{pin_mut, Stream, StreamExt};
+use serde::Serialize;
 
 use crate::{
     errors::CrawlerError,
-    response_processor::traits::{save_data_to_csv, TExtractData, TStoreType},
+    response_processor::{
+        state::CrawlState,
+        traits::{TExtractData, TSink},
+    },
 };
 
 pub mod app_store;
+pub mod atomic;
 pub mod play_store;
+pub mod sink;
+pub mod state;
 pub mod traits;
 
-pub struct RawResponse<T> {
-    responses: Vec<Response>,
-    phantom: PhantomData<T>,
+pub struct ResponseProcessor<D: TExtractData> {
+    extractor: D,
+    sinks: Vec<Box<dyn TSink>>,
+    state_path: Option<PathBuf>,
+    partial_success: bool,
 }
 
-impl<T> RawResponse<T> {
-    pub fn new(responses: Vec<Response>) -> Self {
+impl<D: TExtractData> ResponseProcessor<D> {
+    pub fn new(extractor: D) -> Self {
         Self {
-            responses,
-            phantom: PhantomData,
+            extractor,
+            sinks: Vec::new(),
+            state_path: None,
+            partial_success: false,
+        }
+    }
+
+    /// Adds a sink every page is written to. Several sinks can be added.
+    pub fn with_sink(mut self, sink: Box<dyn TSink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// Crawls incrementally: stops at the newest review recorded in the state
+    /// file and moves the mark forward once the crawl succeeds.
+    pub fn with_state_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.state_path = Some(path.into());
+        self
+    }
+
+    /// Skips pages that cannot be fetched or parsed instead of failing the
+    /// run; the reviews of every other page are still saved. The run only
+    /// fails if no page could be read at all. Skipped pages are returned by
+    /// [`ResponseProcessor::run_with_stats`], and the state file mark is not
+    /// moved so the next run fetches them again.
+    pub fn with_partial_success(mut self) -> Self {
+        self.partial_success = true;
+        self
+    }
+
+    /// Extracts each page as it arrives and writes it straight to every sink.
+    /// Stops pulling pages at the first page that yields no reviews, or, with a
+    /// state file, at the first review written by an earlier crawl. The pages
+    /// written before an error are still saved.
+    pub async fn run<S>(self, pages: S) -> Result<(), CrawlerError>
+    where
+        S: Stream<Item = Result<Bytes, CrawlerError>>,
+    {
+        self.run_with_stats(pages).await.1.map(|_| ())
+    }
+
+    /// Like [`ResponseProcessor::run`], also returning what was crawled,
+    /// including on failure, and the pages skipped under partial success.
+    pub async fn run_with_stats<S>(
+        self,
+        pages: S,
+    ) -> (RunStats, Result<Vec<PageError>, CrawlerError>)
+    where
+        S: Stream<Item = Result<Bytes, CrawlerError>>,
+    {
+        let mut stats = RunStats::default();
+        let result = self.run_inner(pages, &mut stats).await;
+        (stats, result)
+    }
+
+    async fn run_inner<S>(
+        self,
+        pages: S,
+        stats: &mut RunStats,
+    ) -> Result<Vec<PageError>, CrawlerError>
+    where
+        S: Stream<Item = Result<Bytes, CrawlerError>>,
+    {
+        let Self {
+            extractor,
+            mut sinks,
+            state_path,
+            partial_success,
+        } = self;
+        if sinks.is_empty() {
+            tracing::warn!("No sinks configured, extracted reviews are discarded");
+        }
+
+        let seen = match &state_path {
+            Some(path) => CrawlState::load(path)?,
+            None => None,
+        };
+        let mut newest = seen.clone();
+        let mut failed_pages = Vec::new();
+
+        let result = write_pages(
+            &extractor,
+            pages,
+            &mut sinks,
+            seen.as_ref(),
+            &mut newest,
+            stats,
+            partial_success.then_some(&mut failed_pages),
+        )
+        .await;
+
+        // 실패하더라도 그 전까지 받은 페이지는 모든 sink에 저장
+        let mut finish_error = None;
+        for (i, sink) in sinks.into_iter().enumerate() {
+            match sink.finish().await {
+                // 새 리뷰 수는 첫 번째 sink 기준
+                Ok(new_reviews) if i == 0 => stats.new_reviews = new_reviews,
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::error!("Failed to finish sink: {}", e);
+                    finish_error.get_or_insert(e);
+                }
+            }
+        }
+        result?;
+        if let Some(e) = finish_error {
+            return Err(e);
         }
+
+        // 크롤링이 끝까지 성공했을 때만 기록을 옮김 (중간에 실패하면 다음 실행에서 다시 받음)
+        if !failed_pages.is_empty() {
+            tracing::warn!(
+                "{} pages failed, keeping the previous incremental mark",
+                failed_pages.len()
+            );
+        } else if let (Some(path), Some(newest)) = (&state_path, newest) {
+            newest.save(path)?;
+        }
+        Ok(failed_pages)
     }
 }
 
-pub struct ResponseProcessor<D: TExtractData + TStoreType> {
-    data: RawResponse<D>,
-    extractor: D,
-    app_id: String,
+/// What one [`ResponseProcessor`] run crawled.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RunStats {
+    /// Pages received from the crawler.
+    pub pages: u32,
+    /// Reviews extracted from those pages.
+    pub reviews_parsed: usize,
+    /// Entries left out for missing required fields.
+    pub skipped_entries: usize,
+    /// Reviews the first sink did not have yet.
+    pub new_reviews: usize,
 }
 
-impl<D: TExtractData + TStoreType> ResponseProcessor<D> {
-    pub fn new(data: RawResponse<D>, extractor: D, app_id: String) -> Self {
-        Self {
-            data,
-            extractor,
-            app_id,
+/// A page skipped under [`ResponseProcessor::with_partial_success`].
+#[derive(Debug)]
+pub struct PageError {
+    /// 1-based position of the page in the crawl.
+    pub page: u32,
+    pub error: CrawlerError,
+}
+
+/// Writes every page to the sinks. With `failed_pages`, a page that cannot be
+/// fetched or parsed is recorded there and skipped instead of ending the run.
+async fn write_pages<D, S>(
+    extractor: &D,
+    pages: S,
+    sinks: &mut [Box<dyn TSink>],
+    seen: Option<&CrawlState>,
+    newest: &mut Option<CrawlState>,
+    stats: &mut RunStats,
+    mut failed_pages: Option<&mut Vec<PageError>>,
+) -> Result<(), CrawlerError>
+where
+    D: TExtractData,
+    S: Stream<Item = Result<Bytes, CrawlerError>>,
+{
+    pin_mut!(pages);
+    let mut page = 0;
+    let mut read_pages = 0;
+
+    while let Some(bytes) = pages.next().await {
+        page += 1;
+        tracing::debug!("Processing page {}", page);
+
+        let extracted = bytes.and_then(|bytes| {
+            stats.pages += 1;
+            extractor.extract_page(&bytes).map_err(|e| e.on_page(page))
+        });
+        let extracted = match (extracted, failed_pages.as_deref_mut()) {
+            (Ok(extracted), _) => extracted,
+            (Err(error), Some(failed_pages)) => {
+                tracing::warn!("Skipping page {}: {}", page, error);
+                failed_pages.push(PageError { page, error });
+                continue;
+            }
+            (Err(error), None) => return Err(error),
+        };
+        read_pages += 1;
+        stats.reviews_parsed += extracted.reviews.len();
+        stats.skipped_entries += extracted.skipped;
+        let mut data = extracted.reviews;
+        // 불완전한 항목만 있는 페이지는 끝이 아니므로 다음 페이지를 계속 받음
+        if data.is_empty() && extracted.skipped == 0 {
+            tracing::debug!("Page {} returned no entries, stopping pagination", page);
+            break;
+        }
+
+        // 최신순 정렬이므로 이미 본 리뷰부터는 이전 크롤링에서 저장된 리뷰
+        let reached_seen = match seen {
+            Some(seen) => match data.iter().position(|review| seen.has_seen(review)) {
+                Some(position) => {
+                    data.truncate(position);
+                    true
+                }
+                None => false,
+            },
+            None => false,
+        };
+
+        for review in &data {
+            CrawlState::advance(newest, review);
+        }
+        // 첫 리뷰부터 이미 본 리뷰면 쓸 것이 없으므로 sink의 페이지 수에 넣지 않음
+        if !data.is_empty() {
+            for sink in sinks.iter_mut() {
+                sink.write(&data).await?;
+            }
+        }
+
+        if reached_seen {
+            tracing::debug!(
+                "Page {} reached already crawled reviews, stopping pagination",
+                page
+            );
+            break;
+        }
+    }
+
+    // 읽은 페이지가 하나도 없으면 부분 성공이 아니라 실패
+    match failed_pages {
+        Some(failed_pages) if read_pages == 0 && !failed_pages.is_empty() => {
+            Err(failed_pages.remove(0).error)
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::Cell,
+        path::Path,
+        sync::{Arc, Mutex},
+    };
+
+    use async_trait::async_trait;
+    use chrono::{DateTime, TimeZone, Utc};
+    use futures::{stream, StreamExt};
+
+    use super::*;
+    use crate::{
+        response_processor::{
+            sink::{output_path, CsvSink},
+            traits::{ExtractedPage, TStoreType},
+        },
+        review::{Review, Store},
+    };
+
+    // 공백으로 구분된 단어를 리뷰 하나로 취급하는 테스트용 extractor
+    // (숫자 단어는 그 값을 초 단위 날짜로 사용, "!"가 있으면 파싱 오류,
+    // "?"는 필수 필드가 빠진 항목으로 건너뜀)
+    struct Words;
+
+    impl TStoreType for Words {
+        fn store(&self) -> Store {
+            Store::AppStore
+        }
+    }
+
+    impl TExtractData for Words {
+        fn extract_data(&self, response: &[u8]) -> Result<Vec<Review>, CrawlerError> {
+            if response.contains(&b'!') {
+                return Err(CrawlerError::parse(
+                    Some(Store::AppStore),
+                    response,
+                    "unexpected '!'",
+                ));
+            }
+            Ok(String::from_utf8_lossy(response)
+                .split_whitespace()
+                .map(|word| Review {
+                    store: Store::AppStore,
+                    app_id: "app".to_string(),
+                    country: "us".to_string(),
+                    review_id: word.to_string(),
+                    author: String::new(),
+                    author_uri: None,
+                    date: word
+                        .parse()
+                        .map(|secs| Utc.timestamp_opt(secs, 0).unwrap())
+                        .unwrap_or(DateTime::UNIX_EPOCH),
+                    rating: 5,
+                    helpful_votes: 0,
+                    total_votes: None,
+                    version: None,
+                    language: None,
+                    title: None,
+                    body: word.to_string(),
+                    developer_reply: None,
+                })
+                .collect())
+        }
+
+        fn extract_page(&self, response: &[u8]) -> Result<ExtractedPage, CrawlerError> {
+            let (skipped, reviews): (Vec<_>, Vec<_>) = self
+                .extract_data(response)?
+                .into_iter()
+                .partition(|review| review.review_id == "?");
+            Ok(ExtractedPage {
+                reviews,
+                skipped: skipped.len(),
+            })
+        }
+    }
+
+    fn processor(dir: &Path) -> ResponseProcessor<Words> {
+        let sink = CsvSink::append(output_path(dir, Words.store(), "app", "csv")).unwrap();
+        ResponseProcessor::new(Words).with_sink(Box::new(sink))
+    }
+
+    // 받은 review_id, write 호출 수와 finish 호출 여부를 기록하는 테스트용 sink
+    #[derive(Clone, Default)]
+    struct Recording {
+        ids: Arc<Mutex<Vec<String>>>,
+        writes: Arc<Mutex<usize>>,
+        finished: Arc<Mutex<bool>>,
+    }
+
+    #[async_trait]
+    impl TSink for Recording {
+        async fn write(&mut self, reviews: &[Review]) -> Result<(), CrawlerError> {
+            *self.writes.lock().unwrap() += 1;
+            let mut ids = self.ids.lock().unwrap();
+            ids.extend(reviews.iter().map(|review| review.review_id.clone()));
+            Ok(())
         }
+
+        async fn finish(self: Box<Self>) -> Result<usize, CrawlerError> {
+            *self.finished.lock().unwrap() = true;
+            Ok(self.ids.lock().unwrap().len())
+        }
+    }
+
+    fn connection_reset() -> CrawlerError {
+        CrawlerError::io("page", std::io::ErrorKind::ConnectionReset.into())
+    }
+
+    fn pages(bodies: &'static [&'static str]) -> impl Stream<Item = Result<Bytes, CrawlerError>> {
+        stream::iter(
+            bodies
+                .iter()
+                .map(|body| Ok(Bytes::from_static(body.as_bytes()))),
+        )
+    }
+
+    // CSV에서 review_id 컬럼만 읽음
+    fn written_ids(dir: &Path) -> Vec<String> {
+        let mut reader = csv::Reader::from_path(dir.join("app_store/app.csv")).unwrap();
+        reader
+            .records()
+            .map(|record| record.unwrap()[3].to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_run_writes_every_page() {
+        let dir = tempfile::tempdir().unwrap();
+        let processor = processor(dir.path());
+
+        processor.run(pages(&["a b", "c"])).await.unwrap();
+
+        assert_eq!(written_ids(dir.path()), ["a", "b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn test_run_with_stats_counts_pages_and_new_reviews() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let (stats, result) = processor(dir.path())
+            .run_with_stats(pages(&["a b", "c", ""]))
+            .await;
+        result.unwrap();
+        assert_eq!(
+            stats,
+            RunStats {
+                pages: 3,
+                reviews_parsed: 3,
+                skipped_entries: 0,
+                new_reviews: 3,
+            }
+        );
+
+        // 이미 파일에 있는 리뷰는 새 리뷰로 세지 않음
+        let (stats, _) = processor(dir.path()).run_with_stats(pages(&["c d"])).await;
+        assert_eq!(stats.reviews_parsed, 2);
+        assert_eq!(stats.new_reviews, 1);
+    }
+
+    #[tokio::test]
+    async fn test_run_stops_at_first_empty_page() {
+        let dir = tempfile::tempdir().unwrap();
+        let processor = processor(dir.path());
+
+        let polled = Cell::new(0);
+        let stream = pages(&["a", "   ", "b", "c"]).inspect(|_| polled.set(polled.get() + 1));
+        processor.run(stream).await.unwrap();
+
+        // 빈 페이지 이후의 페이지는 요청되지 않아야 함
+        assert_eq!(polled.get(), 2);
+        assert_eq!(written_ids(dir.path()), ["a"]);
     }
 
-    pub async fn run(self) -> Result<(), CrawlerError> {
-        let mut all_data = Vec::new();
-        let responses_count = self.data.responses.len();
+    #[tokio::test]
+    async fn test_run_continues_after_page_of_incomplete_entries() {
+        let dir = tempfile::tempdir().unwrap();
 
-        // 모든 응답에서 데이터 추출
-        for (i, response) in self.data.responses.into_iter().enumerate() {
-            tracing::debug!("Processing response {}/{}", i + 1, responses_count);
+        let (stats, result) = processor(dir.path())
+            .run_with_stats(pages(&["a", "? ?", "b", ""]))
+            .await;
+        result.unwrap();
 
-            let bytes = response
-                .bytes()
-                .await
-                .map_err(|e| CrawlerError::Request(e.to_string()))?;
+        assert_eq!(stats.pages, 4);
+        assert_eq!(stats.skipped_entries, 2);
+        assert_eq!(written_ids(dir.path()), ["a", "b"]);
+    }
 
-            let data = self.extractor.extract_data(&bytes)?;
-            all_data.extend(data);
+    #[tokio::test]
+    async fn test_run_keeps_pages_written_before_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let processor = processor(dir.path());
+
+        let stream = stream::iter(vec![Ok(Bytes::from_static(b"a")), Err(connection_reset())]);
+        let result = processor.run(stream).await;
+
+        assert!(matches!(result, Err(CrawlerError::Io { .. })));
+        assert_eq!(written_ids(dir.path()), ["a"]);
+    }
+
+    #[tokio::test]
+    async fn test_parse_error_names_the_page() {
+        let dir = tempfile::tempdir().unwrap();
+        let result = processor(dir.path()).run(pages(&["a", "b !"])).await;
+
+        match result {
+            Err(CrawlerError::Parse { store, page, .. }) => {
+                assert_eq!(store, Some(Store::AppStore));
+                assert_eq!(page, Some(2));
+            }
+            other => panic!("Expected Parse error, got {other:?}"),
         }
+        assert_eq!(written_ids(dir.path()), ["a"]);
+    }
+
+    #[tokio::test]
+    async fn test_partial_success_skips_failed_pages() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = dir.path().join("state.json");
+
+        let stream = stream::iter(vec![
+            Ok(Bytes::from_static(b"a")),
+            Err(connection_reset()),
+            Ok(Bytes::from_static(b"b !")),
+            Ok(Bytes::from_static(b"c")),
+        ]);
+        let (stats, result) = processor(dir.path())
+            .with_state_file(&state)
+            .with_partial_success()
+            .run_with_stats(stream)
+            .await;
+
+        let failed: Vec<(u32, &str)> = result
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|failed| (failed.page, failed.error.kind()))
+            .collect();
+        assert_eq!(failed, [(2, "io"), (3, "parse")]);
+        assert_eq!(stats.pages, 3);
+        assert_eq!(stats.reviews_parsed, 2);
+        assert_eq!(written_ids(dir.path()), ["a", "c"]);
+        // 건너뛴 페이지를 다음 실행에서 다시 받도록 기록을 옮기지 않음
+        assert!(CrawlState::load(&state).unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_partial_success_fails_when_no_page_is_read() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let stream = stream::iter(vec![Err(connection_reset()), Ok(Bytes::from_static(b"!"))]);
+        let result = processor(dir.path())
+            .with_partial_success()
+            .run(stream)
+            .await;
+
+        assert!(matches!(result, Err(CrawlerError::Io { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_incremental_run_stops_at_already_crawled_reviews() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = dir.path().join("state.json");
+
+        processor(dir.path())
+            .with_state_file(&state)
+            .run(pages(&["5 4", "3 2", "1"]))
+            .await
+            .unwrap();
+        assert_eq!(written_ids(dir.path()), ["5", "4", "3", "2", "1"]);
+        let mark = CrawlState::load(&state).unwrap().unwrap();
+        assert_eq!(mark.newest_review_id, "5");
+
+        // 두 번째 실행은 새 리뷰만 이어 붙이고 이미 본 리뷰에서 멈춤
+        let polled = Cell::new(0);
+        let stream = pages(&["7 6", "5 4", "3"]).inspect(|_| polled.set(polled.get() + 1));
+        processor(dir.path())
+            .with_state_file(&state)
+            .run(stream)
+            .await
+            .unwrap();
 
-        // 모든 데이터를 하나의 CSV 파일로 저장
-        save_data_to_csv(all_data, &self.extractor, &self.app_id)?;
-        Ok(())
+        assert_eq!(polled.get(), 2);
+        assert_eq!(written_ids(dir.path()), ["5", "4", "3", "2", "1", "7", "6"]);
+        let mark = CrawlState::load(&state).unwrap().unwrap();
+        assert_eq!(mark.newest_review_id, "7");
+    }
+
+    #[tokio::test]
+    async fn test_page_of_only_seen_reviews_is_not_written() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = dir.path().join("state.json");
+        processor(dir.path())
+            .with_state_file(&state)
+            .run(pages(&["5"]))
+            .await
+            .unwrap();
+
+        let sink = Recording::default();
+        ResponseProcessor::new(Words)
+            .with_sink(Box::new(sink.clone()))
+            .with_state_file(&state)
+            .run(pages(&["5 4"]))
+            .await
+            .unwrap();
+
+        assert_eq!(*sink.writes.lock().unwrap(), 0);
+        assert!(*sink.finished.lock().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_failed_incremental_run_keeps_the_old_mark() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = dir.path().join("state.json");
+        processor(dir.path())
+            .with_state_file(&state)
+            .run(pages(&["5"]))
+            .await
+            .unwrap();
+
+        let stream = stream::iter(vec![Ok(Bytes::from_static(b"7")), Err(connection_reset())]);
+        let result = processor(dir.path())
+            .with_state_file(&state)
+            .run(stream)
+            .await;
+
+        assert!(result.is_err());
+        let mark = CrawlState::load(&state).unwrap().unwrap();
+        assert_eq!(mark.newest_review_id, "5");
+    }
+
+    #[tokio::test]
+    async fn test_run_writes_to_every_sink() {
+        let first = Recording::default();
+        let second = Recording::default();
+        let processor = ResponseProcessor::new(Words)
+            .with_sink(Box::new(first.clone()))
+            .with_sink(Box::new(second.clone()));
+
+        let stream = stream::iter(vec![
+            Ok(Bytes::from_static(b"a b")),
+            Err(connection_reset()),
+        ]);
+        let result = processor.run(stream).await;
+
+        // 에러가 나도 모든 sink가 그때까지의 리뷰를 받고 finish됨
+        assert!(result.is_err());
+        for sink in [first, second] {
+            assert_eq!(*sink.ids.lock().unwrap(), ["a", "b"]);
+            assert!(*sink.finished.lock().unwrap());
+        }
     }
 }
@@ -1,43 +1,318 @@
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
 
 use crate::{
-    errors::CrawlerError,
-    response_processor::traits::{TExtractData, TStoreType},
-    OUTPUT_PATH,
+    errors::{BoxError, CrawlerError},
+    response_processor::traits::{ExtractedPage, TExtractData, TStoreType},
+    review::{DeveloperReply, Review, Store},
 };
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PlayStoreReview {
-    pub date: String,
-    pub star: i32,
-    pub like: i32,
-    pub dislike: i32,
-    pub title: String,
-    pub review: String,
+// batchexecute 응답 앞에 붙는 XSSI 방지용 prefix
+const XSSI_PREFIX: &str = ")]}'";
+
+/// Maps Play Store batchexecute review pages into [`Review`]s.
+#[derive(Debug, Clone)]
+pub struct PlayStoreExtractor {
+    app_id: String,
+    country: String,
+    language: String,
 }
 
-impl PlayStoreReview {
-    pub fn new() -> Self {
+impl PlayStoreExtractor {
+    /// `language` is the one the reviews were requested in (the client's
+    /// `hl`), recorded on every review since the payload does not carry it.
+    pub fn new(
+        app_id: impl Into<String>,
+        country: impl Into<String>,
+        language: impl Into<String>,
+    ) -> Self {
         Self {
-            date: String::new(),
-            star: 0,
-            like: 0,
-            dislike: 0,
-            title: String::new(),
-            review: String::new(),
+            app_id: app_id.into(),
+            country: country.into(),
+            language: language.into(),
+        }
+    }
+}
+
+impl TStoreType for PlayStoreExtractor {
+    fn store(&self) -> Store {
+        Store::PlayStore
+    }
+}
+
+impl TExtractData for PlayStoreExtractor {
+    fn extract_data(&self, response: &[u8]) -> Result<Vec<Review>, CrawlerError> {
+        self.extract_page(response).map(|page| page.reviews)
+    }
+
+    fn extract_page(&self, response: &[u8]) -> Result<ExtractedPage, CrawlerError> {
+        tracing::debug!("Starting Play Store payload parsing");
+
+        let payload = parse_payload(response)?;
+        let entries = match payload.get(0).and_then(Value::as_array) {
+            Some(entries) => entries,
+            None => {
+                tracing::debug!("Payload contains no review list");
+                return Ok(ExtractedPage::default());
+            }
+        };
+
+        let mut page = ExtractedPage::default();
+        for entry in entries {
+            match self.parse_entry(entry)? {
+                Some(review) => page.reviews.push(review),
+                None => {
+                    tracing::debug!("Skipped incomplete entry");
+                    page.skipped += 1;
+                }
+            }
         }
+
+        tracing::debug!(
+            "Play Store parsing completed. Found {} reviews ({} skipped)",
+            page.reviews.len(),
+            page.skipped
+        );
+        Ok(page)
+    }
+}
+
+impl PlayStoreExtractor {
+    // 리뷰 배열 레이아웃:
+    // [0] review id, [1] [author name, ..], [2] star, [4] text,
+    // [5] [seconds, nanos], [6] thumbs up, [7] developer reply, [10] app version
+    //
+    // 필수 필드가 없으면 Ok(None), 날짜를 해석할 수 없으면 에러
+    fn parse_entry(&self, entry: &Value) -> Result<Option<Review>, CrawlerError> {
+        // Play Store 리뷰에는 제목이 없으므로 본문만 필수
+        let body = match entry.get(4).and_then(Value::as_str) {
+            Some(body) if !body.is_empty() => body.to_string(),
+            _ => return Ok(None),
+        };
+        // 빈 ID는 중복 제거 키로 쓸 수 없으므로 불완전한 항목으로 취급
+        let Some(review_id) = entry
+            .get(0)
+            .and_then(Value::as_str)
+            .filter(|id| !id.trim().is_empty())
+        else {
+            return Ok(None);
+        };
+        let date = match entry.get(5) {
+            Some(timestamp) if !timestamp.is_null() => parse_timestamp(timestamp, review_id)?,
+            _ => return Ok(None),
+        };
+        let developer_reply = match entry.get(7) {
+            Some(reply) => Self::parse_reply(reply, review_id)?,
+            None => None,
+        };
+
+        Ok(Some(Review {
+            store: Store::PlayStore,
+            app_id: self.app_id.clone(),
+            country: self.country.clone(),
+            review_id: review_id.to_string(),
+            author: Self::read_str(entry.get(1), 0).unwrap_or_default(),
+            author_uri: None,
+            date,
+            rating: Self::read_u32(entry, 2).try_into().unwrap_or(0),
+            helpful_votes: Self::read_u32(entry, 6),
+            total_votes: None,
+            version: Self::read_str(Some(entry), 10),
+            language: Some(self.language.clone()),
+            title: None,
+            body,
+            developer_reply,
+        }))
+    }
+
+    // [null, "<reply text>", [seconds, nanos]]
+    fn parse_reply(reply: &Value, review_id: &str) -> Result<Option<DeveloperReply>, CrawlerError> {
+        let Some(body) = Self::read_str(Some(reply), 1) else {
+            return Ok(None);
+        };
+        let date = match reply.get(2) {
+            Some(timestamp) if !timestamp.is_null() => Some(parse_timestamp(timestamp, review_id)?),
+            _ => None,
+        };
+        Ok(Some(DeveloperReply { body, date }))
+    }
+
+    fn read_str(value: Option<&Value>, index: usize) -> Option<String> {
+        value?
+            .get(index)?
+            .as_str()
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
     }
+
+    fn read_u32(entry: &Value, index: usize) -> u32 {
+        entry
+            .get(index)
+            .and_then(Value::as_u64)
+            .and_then(|n| u32::try_from(n).ok())
+            .unwrap_or(0)
+    }
+}
+
+/// Converts a `[seconds, nanos]` Unix timestamp into UTC.
+fn parse_timestamp(timestamp: &Value, review_id: &str) -> Result<DateTime<Utc>, CrawlerError> {
+    let seconds = timestamp.get(0).and_then(Value::as_i64);
+    let nanos = match timestamp.get(1) {
+        None | Some(Value::Null) => Some(0),
+        Some(nanos) => nanos.as_u64().and_then(|n| u32::try_from(n).ok()),
+    };
+    seconds
+        .zip(nanos)
+        .and_then(|(seconds, nanos)| DateTime::from_timestamp(seconds, nanos))
+        .ok_or_else(|| {
+            CrawlerError::parse(
+                Some(Store::PlayStore),
+                timestamp.to_string().as_bytes(),
+                format!("Invalid timestamp in review {review_id}"),
+            )
+        })
 }
 
-impl TStoreType for PlayStoreReview {
-    fn get_output_path(&self, app_id: &str) -> String {
-        format!("{OUTPUT_PATH}/play_store/{app_id}.csv")
+/// Unwraps the `)]}'`-prefixed batchexecute envelope and returns the decoded
+/// inner payload of the review RPC (`Value::Null` when the RPC returned nothing).
+pub(crate) fn parse_payload(response: &[u8]) -> Result<Value, CrawlerError> {
+    let parse_error = |e: BoxError| CrawlerError::parse(Some(Store::PlayStore), response, e);
+    let text = std::str::from_utf8(response).map_err(|e| parse_error(e.into()))?;
+    let text = text.trim_start();
+    let text = text.strip_prefix(XSSI_PREFIX).unwrap_or(text);
+
+    let envelope: Value = serde_json::from_str(text.trim()).map_err(|e| parse_error(e.into()))?;
+
+    // [["wrb.fr", "<rpc id>", "<json string>", ...], ["di", ..], ["af.httprm", ..]]
+    let inner = envelope
+        .as_array()
+        .and_then(|items| {
+            items
+                .iter()
+                .find(|item| item.get(0).and_then(Value::as_str) == Some("wrb.fr"))
+        })
+        .ok_or_else(|| parse_error("Missing wrb.fr entry in response".into()))?;
+
+    match inner.get(2) {
+        Some(Value::String(payload)) => serde_json::from_str(payload)
+            .map_err(|e| CrawlerError::parse(Some(Store::PlayStore), payload.as_bytes(), e)),
+        _ => Ok(Value::Null),
     }
 }
 
-impl TExtractData for PlayStoreReview {
-    fn extract_data(&self, _response: &[u8]) -> Result<Vec<Self>, CrawlerError> {
-        // TODO: Play Store review parsing not implemented yet
-        todo!("Play Store review parsing not implemented yet")
+/// Returns the continuation token for the next page, or `None` once the feed is exhausted.
+pub(crate) fn next_page_token(response: &[u8]) -> Option<String> {
+    let payload = parse_payload(response).ok()?;
+    // [[reviews..], [null, "<token>"]]
+    payload
+        .get(1)
+        .and_then(|page| page.get(1))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extractor() -> PlayStoreExtractor {
+        PlayStoreExtractor::new("com.example.app", "us", "en")
+    }
+
+    const PAGE: &str = include_str!("../../tests/fixtures/play_store/reviews_page.txt");
+    const LAST_PAGE: &str = include_str!("../../tests/fixtures/play_store/reviews_last_page.txt");
+    const EMPTY: &str = include_str!("../../tests/fixtures/play_store/reviews_empty.txt");
+
+    #[test]
+    fn test_extract_play_store_reviews() {
+        let extractor = extractor();
+        let reviews = extractor.extract_data(PAGE.as_bytes()).unwrap();
+
+        // 세 번째 리뷰는 본문이 없으므로 건너뛴다
+        assert_eq!(reviews.len(), 2);
+
+        let first = &reviews[0];
+        assert_eq!(first.store, Store::PlayStore);
+        assert_eq!(first.app_id, "com.example.app");
+        assert_eq!(
+            first.review_id,
+            "gp:AOqpTOGmHkqX1nV9uZsT3bQwPjR4xYcL8dNfA2eK7vM"
+        );
+        assert_eq!(first.author, "Jamie Rivera");
+        assert_eq!(first.rating, 5);
+        assert_eq!(first.helpful_votes, 12);
+        assert_eq!(first.total_votes, None);
+        assert_eq!(first.date.to_rfc3339(), "2025-05-11T21:46:40.123+00:00");
+        assert_eq!(first.version.as_deref(), Some("2.25.14.77"));
+        assert_eq!(first.language.as_deref(), Some("en"));
+        assert!(first.title.is_none());
+        assert!(first.body.starts_with("Works great for keeping in touch"));
+        assert!(first.developer_reply.is_none());
+
+        let second = &reviews[1];
+        assert_eq!(second.rating, 2);
+        assert_eq!(second.helpful_votes, 3);
+        assert!(second.body.contains("crashes when I open a chat"));
+        let reply = second.developer_reply.as_ref().unwrap();
+        assert!(reply.body.starts_with("Sorry about that!"));
+        assert_eq!(
+            reply.date.unwrap().to_rfc3339(),
+            "2025-05-11T07:53:20+00:00"
+        );
+    }
+
+    #[test]
+    fn test_extract_play_store_last_page() {
+        let extractor = extractor();
+        let reviews = extractor.extract_data(LAST_PAGE.as_bytes()).unwrap();
+
+        assert_eq!(reviews.len(), 1);
+        assert_eq!(reviews[0].rating, 3);
+        assert_eq!(reviews[0].helpful_votes, 1);
+    }
+
+    #[test]
+    fn test_extract_play_store_empty_payload() {
+        let extractor = extractor();
+        let reviews = extractor.extract_data(EMPTY.as_bytes()).unwrap();
+        assert!(reviews.is_empty());
+    }
+
+    #[test]
+    fn test_malformed_timestamp_is_a_parse_error() {
+        let page = r#")]}'
+
+[["wrb.fr","UsvDTd","[[[\"gp:1\",[\"Kim\"],4,null,\"Nice\",[\"soon\",0],0]]]",null,null,null,"generic"]]"#;
+
+        let result = extractor().extract_data(page.as_bytes());
+
+        match result {
+            Err(error @ CrawlerError::Parse { .. }) => {
+                assert!(error.to_string().contains("gp:1"));
+                assert!(error.to_string().contains("soon"));
+            }
+            other => panic!("Expected a parse error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_next_page_token() {
+        let token = next_page_token(PAGE.as_bytes()).unwrap();
+        assert!(token.starts_with("CsYBCnZBSmE0"));
+
+        assert!(next_page_token(LAST_PAGE.as_bytes()).is_none());
+        assert!(next_page_token(EMPTY.as_bytes()).is_none());
+        assert!(next_page_token(b"garbage").is_none());
+    }
+
+    #[test]
+    fn test_extract_play_store_invalid_payload() {
+        let extractor = extractor();
+
+        let result = extractor.extract_data(b")]}'\n\n<html>not json</html>");
+        assert!(matches!(result, Err(CrawlerError::Parse { .. })));
+
+        let result = extractor.extract_data(b")]}'\n\n[[\"di\",47]]");
+        assert!(matches!(result, Err(CrawlerError::Parse { .. })));
     }
 }
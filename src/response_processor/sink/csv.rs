@@ -0,0 +1,376 @@
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+    path::Path,
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use csv::StringRecord;
+use serde::Serialize;
+
+use crate::{
+    errors::CrawlerError,
+    response_processor::{
+        atomic::AtomicFile,
+        sink::{review_key, ReviewKey},
+        traits::TSink,
+    },
+    review::Review,
+};
+
+/// Column order of the CSV output (the field order of `CsvRow`).
+pub const CSV_COLUMNS: [&str; 16] = [
+    "store",
+    "app_id",
+    "country",
+    "review_id",
+    "date",
+    "rating",
+    "helpful_votes",
+    "total_votes",
+    "author",
+    "author_uri",
+    "version",
+    "language",
+    "title",
+    "body",
+    "reply_body",
+    "reply_date",
+];
+
+/// CSV output that is written page by page. Records are appended after the
+/// reviews already in the file and reviews already there are skipped, so
+/// history is kept across runs and a crawl can safely be repeated. Everything
+/// goes to a temporary file that only replaces the target when the sink is
+/// finished, so readers never see a half-written file.
+pub struct CsvSink {
+    writer: csv::Writer<AtomicFile>,
+    seen: HashSet<ReviewKey>,
+    written: usize,
+    skipped: usize,
+}
+
+impl CsvSink {
+    /// Starts a new version of `path` holding the reviews already in it. The
+    /// header is only written when there are none; an existing file must have
+    /// the same header.
+    pub fn append(path: impl AsRef<Path>) -> Result<Self, CrawlerError> {
+        let path = path.as_ref();
+        let mut file = AtomicFile::create(path)?;
+
+        let seen = match File::open(path) {
+            Ok(mut existing) => {
+                let (seen, complete_len) = read_existing_keys(&mut existing, path)?;
+                // 기존 파일에서 완전한 레코드까지만 새 파일로 복사
+                existing
+                    .rewind()
+                    .and_then(|_| io::copy(&mut existing.take(complete_len), &mut file))
+                    .map_err(|e| CrawlerError::io(path, e))?;
+                if complete_len == 0 {
+                    None
+                } else {
+                    Some(seen)
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(CrawlerError::io(path, e)),
+        };
+
+        let writer = csv::WriterBuilder::new()
+            .has_headers(seen.is_none())
+            .from_writer(file);
+
+        Ok(Self {
+            writer,
+            seen: seen.unwrap_or_default(),
+            written: 0,
+            skipped: 0,
+        })
+    }
+}
+
+#[async_trait]
+impl TSink for CsvSink {
+    /// Writes the reviews that are not in the file yet.
+    async fn write(&mut self, data: &[Review]) -> Result<(), CrawlerError> {
+        for review in data {
+            if !self.seen.insert(review_key(review)) {
+                self.skipped += 1;
+                continue;
+            }
+            if let Err(e) = self.writer.serialize(CsvRow::from(review)) {
+                return Err(CrawlerError::io(self.writer.get_ref().path(), e.into()));
+            }
+            self.written += 1;
+        }
+
+        if let Err(e) = self.writer.flush() {
+            return Err(CrawlerError::io(self.writer.get_ref().path(), e));
+        }
+        Ok(())
+    }
+
+    /// Replaces the target with the new file and returns the number of new
+    /// records written.
+    async fn finish(self: Box<Self>) -> Result<usize, CrawlerError> {
+        let path = self.writer.get_ref().path().to_path_buf();
+        let file = self
+            .writer
+            .into_inner()
+            .map_err(|e| CrawlerError::io(&path, e.into_error()))?;
+        tracing::debug!(
+            "Wrote {} records to {} ({} already present)",
+            self.written,
+            file.path().display(),
+            self.skipped
+        );
+        file.commit()?;
+        Ok(self.written)
+    }
+}
+
+/// Reads the keys of the reviews already in the file, and the length of the
+/// file up to its last complete record (a record cut off by an interrupted
+/// run is left out).
+fn read_existing_keys(
+    file: &mut File,
+    path: &Path,
+) -> Result<(HashSet<ReviewKey>, u64), CrawlerError> {
+    let io_error = |e: io::Error| CrawlerError::io(path, e);
+
+    let mut keys = HashSet::new();
+    let len = file.seek(SeekFrom::End(0)).map_err(io_error)?;
+    if len == 0 {
+        return Ok((keys, 0));
+    }
+    let mut last_byte = [0u8];
+    file.seek(SeekFrom::End(-1)).map_err(io_error)?;
+    file.read_exact(&mut last_byte).map_err(io_error)?;
+    let ends_with_newline = last_byte[0] == b'\n';
+    file.rewind().map_err(io_error)?;
+
+    let mut reader = csv::Reader::from_reader(&mut *file);
+    let headers = reader
+        .headers()
+        .map_err(|e| CrawlerError::parse(None, b"", format!("{}: {e}", path.display())))?;
+    if headers.iter().ne(CSV_COLUMNS) {
+        let header = headers.iter().collect::<Vec<_>>().join(",");
+        return Err(CrawlerError::parse(
+            None,
+            header.as_bytes(),
+            format!(
+                "{} has a different header than the current output format; move it aside to start a new file",
+                path.display()
+            ),
+        ));
+    }
+
+    let mut record = StringRecord::new();
+    let mut complete_len = len;
+    loop {
+        let start = reader.position().byte();
+        match reader.read_record(&mut record) {
+            Ok(false) => break,
+            Ok(true) => {
+                // 줄바꿈 없이 끝난 마지막 레코드는 쓰다가 중단된 것
+                if reader.position().byte() == len && !ends_with_newline {
+                    complete_len = start;
+                    break;
+                }
+                keys.insert((
+                    record[0].to_string(),
+                    record[1].to_string(),
+                    record[2].to_string(),
+                    record[3].to_string(),
+                ));
+            }
+            // 필드 수가 모자란 레코드는 마지막 레코드일 때만 잘린 것으로 봄
+            Err(e) if matches!(e.kind(), csv::ErrorKind::UnequalLengths { .. }) => {
+                if matches!(reader.read_record(&mut record), Ok(false)) {
+                    complete_len = start;
+                    break;
+                }
+                return Err(CrawlerError::parse(
+                    None,
+                    b"",
+                    format!("Malformed record in {}: {e}", path.display()),
+                ));
+            }
+            Err(e) => {
+                return Err(CrawlerError::parse(
+                    None,
+                    b"",
+                    format!("Failed to read {}: {e}", path.display()),
+                ))
+            }
+        }
+    }
+
+    if complete_len < len {
+        tracing::warn!(
+            "Dropping incomplete last record of {} (interrupted run?)",
+            path.display()
+        );
+    }
+    Ok((keys, complete_len))
+}
+
+// CSV는 중첩 구조를 쓸 수 없으므로 개발자 답변을 컬럼으로 펼친 행
+#[derive(Serialize)]
+struct CsvRow<'a> {
+    store: &'a str,
+    app_id: &'a str,
+    country: &'a str,
+    review_id: &'a str,
+    date: DateTime<Utc>,
+    rating: u8,
+    helpful_votes: u32,
+    total_votes: Option<u32>,
+    author: &'a str,
+    author_uri: Option<&'a str>,
+    version: Option<&'a str>,
+    language: Option<&'a str>,
+    title: Option<&'a str>,
+    body: &'a str,
+    reply_body: Option<&'a str>,
+    reply_date: Option<DateTime<Utc>>,
+}
+
+impl<'a> From<&'a Review> for CsvRow<'a> {
+    fn from(review: &'a Review) -> Self {
+        let reply = review.developer_reply.as_ref();
+        Self {
+            store: review.store.as_str(),
+            app_id: &review.app_id,
+            country: &review.country,
+            review_id: &review.review_id,
+            date: review.date,
+            rating: review.rating,
+            helpful_votes: review.helpful_votes,
+            total_votes: review.total_votes,
+            author: &review.author,
+            author_uri: review.author_uri.as_deref(),
+            version: review.version.as_deref(),
+            language: review.language.as_deref(),
+            title: review.title.as_deref(),
+            body: &review.body,
+            reply_body: reply.map(|reply| reply.body.as_str()),
+            reply_date: reply.and_then(|reply| reply.date),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    fn ids(path: &Path) -> Vec<String> {
+        let mut reader = csv::Reader::from_path(path).unwrap();
+        assert_eq!(
+            reader.headers().unwrap(),
+            &StringRecord::from(CSV_COLUMNS.to_vec())
+        );
+        reader
+            .records()
+            .map(|record| record.unwrap()[3].to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_header_matches_columns() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("reviews.csv");
+
+        let mut sink = CsvSink::append(&path).unwrap();
+        sink.write(&[Review::sample("1")]).await.unwrap();
+        Box::new(sink).finish().await.unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content.lines().next().unwrap(), CSV_COLUMNS.join(","));
+    }
+
+    #[tokio::test]
+    async fn test_append_skips_reviews_already_in_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("reviews.csv");
+
+        let mut sink = CsvSink::append(&path).unwrap();
+        sink.write(&[Review::sample("1"), Review::sample("2")])
+            .await
+            .unwrap();
+        assert_eq!(Box::new(sink).finish().await.unwrap(), 2);
+
+        // 다시 실행해도 헤더와 기존 리뷰는 한 번만 남음
+        let mut sink = CsvSink::append(&path).unwrap();
+        sink.write(&[Review::sample("3"), Review::sample("2")])
+            .await
+            .unwrap();
+        sink.write(&[Review::sample("3"), Review::sample("1")])
+            .await
+            .unwrap();
+        assert_eq!(Box::new(sink).finish().await.unwrap(), 1);
+
+        assert_eq!(ids(&path), ["1", "2", "3"]);
+    }
+
+    #[tokio::test]
+    async fn test_append_removes_record_cut_off_by_interrupted_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("reviews.csv");
+        let review = |id| Review {
+            body: "multi\nline body".to_string(),
+            ..Review::sample(id)
+        };
+
+        let mut sink = CsvSink::append(&path).unwrap();
+        sink.write(&[review("1"), review("2")]).await.unwrap();
+        Box::new(sink).finish().await.unwrap();
+
+        // 두 번째 레코드의 본문 중간(따옴표 안 줄바꿈 직후)에서 잘린 파일
+        let content = fs::read_to_string(&path).unwrap();
+        let cut = content.rfind("line body").unwrap();
+        fs::write(&path, &content[..cut]).unwrap();
+
+        let mut sink = CsvSink::append(&path).unwrap();
+        sink.write(&[review("2"), review("3")]).await.unwrap();
+        assert_eq!(Box::new(sink).finish().await.unwrap(), 2);
+
+        assert_eq!(ids(&path), ["1", "2", "3"]);
+    }
+
+    #[tokio::test]
+    async fn test_target_is_only_replaced_on_finish() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("reviews.csv");
+
+        let mut sink = CsvSink::append(&path).unwrap();
+        sink.write(&[Review::sample("1")]).await.unwrap();
+        Box::new(sink).finish().await.unwrap();
+        let before = fs::read_to_string(&path).unwrap();
+
+        // 중간에 버려진 sink는 기존 파일을 건드리지 않음
+        let mut sink = CsvSink::append(&path).unwrap();
+        sink.write(&[Review::sample("2")]).await.unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), before);
+        drop(sink);
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), before);
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_append_refuses_file_with_other_header() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("reviews.csv");
+        fs::write(&path, "date,star,like,dislike,title,review\n").unwrap();
+
+        assert!(matches!(
+            CsvSink::append(&path),
+            Err(CrawlerError::Parse { .. })
+        ));
+    }
+}
@@ -0,0 +1,217 @@
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::CrawlerError,
+    response_processor::traits::TSink,
+    review::{Review, Store},
+};
+
+pub mod csv;
+pub mod ndjson;
+pub mod parquet;
+pub mod postgres;
+pub mod sqlite;
+
+pub use self::{
+    csv::CsvSink,
+    ndjson::NdjsonSink,
+    parquet::ParquetSink,
+    postgres::{PostgresDatabase, PostgresSink},
+    sqlite::SqliteSink,
+};
+
+/// Read by the `postgres` sink when the config gives no `url`.
+pub const DATABASE_URL_ENV: &str = "DATABASE_URL";
+
+/// An output selected in the `sinks` section of the config file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SinkConfig {
+    /// `output/<store>/<app_id>.csv`
+    Csv,
+    /// `output/<store>/<app_id>.jsonl`, or `.jsonl.gz` with `gzip`
+    Ndjson {
+        #[serde(default)]
+        gzip: bool,
+    },
+    /// `output/parquet/store=<store>/app_id=<app_id>/crawl_date=<date>/`, one file per crawl
+    Parquet,
+    /// One database for all apps, `output/reviews.db` unless `path` is given
+    Sqlite {
+        #[serde(default)]
+        path: Option<PathBuf>,
+    },
+    /// Shared database at `url`, or at `$DATABASE_URL` when it is left out
+    Postgres {
+        #[serde(default)]
+        url: Option<String>,
+    },
+}
+
+/// Sinks used when the config file selects none.
+pub fn default_sinks() -> Vec<SinkConfig> {
+    vec![SinkConfig::Csv]
+}
+
+impl SinkConfig {
+    /// Sets up what every app of a run shares: the `postgres` sink connects
+    /// and migrates here, once, instead of in every [`SinkConfig::open`].
+    pub async fn prepare(&self) -> Result<PreparedSink, CrawlerError> {
+        match self {
+            SinkConfig::Postgres { url } => Ok(PreparedSink::Postgres(
+                PostgresDatabase::connect(&postgres_url(url)?).await?,
+            )),
+            config => Ok(PreparedSink::Config(config.clone())),
+        }
+    }
+
+    /// Opens the sink for one app. A `postgres` sink gets a connection of
+    /// its own; runs over several apps [`prepare`](SinkConfig::prepare) it
+    /// once instead.
+    pub async fn open(
+        &self,
+        output_dir: &Path,
+        store: Store,
+        app_id: &str,
+        country: &str,
+    ) -> Result<Box<dyn TSink>, CrawlerError> {
+        match self {
+            SinkConfig::Csv => Ok(Box::new(CsvSink::append(output_path(
+                output_dir, store, app_id, "csv",
+            ))?)),
+            SinkConfig::Ndjson { gzip } => {
+                let extension = if *gzip { "jsonl.gz" } else { "jsonl" };
+                Ok(Box::new(NdjsonSink::append(
+                    output_path(output_dir, store, app_id, extension),
+                    *gzip,
+                )?))
+            }
+            SinkConfig::Parquet => Ok(Box::new(ParquetSink::create(
+                output_dir,
+                store,
+                app_id,
+                country,
+                Utc::now(),
+            ))),
+            SinkConfig::Sqlite { path } => {
+                let path = path
+                    .clone()
+                    .unwrap_or_else(|| output_dir.join("reviews.db"));
+                Ok(Box::new(
+                    SqliteSink::open(path, store, app_id, country).await?,
+                ))
+            }
+            SinkConfig::Postgres { url } => Ok(Box::new(
+                PostgresSink::connect(&postgres_url(url)?, store, app_id, country).await?,
+            )),
+        }
+    }
+}
+
+/// A sink of the run, with its shared resources set up by
+/// [`SinkConfig::prepare`].
+#[derive(Debug, Clone)]
+pub enum PreparedSink {
+    /// Sinks that open per app
+    Config(SinkConfig),
+    /// One connection shared by every app
+    Postgres(PostgresDatabase),
+}
+
+impl PreparedSink {
+    /// Opens the sink for one app.
+    pub async fn open(
+        &self,
+        output_dir: &Path,
+        store: Store,
+        app_id: &str,
+        country: &str,
+    ) -> Result<Box<dyn TSink>, CrawlerError> {
+        match self {
+            PreparedSink::Config(config) => config.open(output_dir, store, app_id, country).await,
+            PreparedSink::Postgres(database) => {
+                Ok(Box::new(database.sink(store, app_id, country).await?))
+            }
+        }
+    }
+}
+
+fn postgres_url(url: &Option<String>) -> Result<String, CrawlerError> {
+    match url {
+        Some(url) => Ok(url.clone()),
+        None => std::env::var(DATABASE_URL_ENV).map_err(|e| {
+            CrawlerError::config(
+                Some("sinks.url"),
+                format!("postgres sink needs a `url` or {DATABASE_URL_ENV}: {e}"),
+            )
+        }),
+    }
+}
+
+/// Identifies a review across sinks: (store, app_id, country, review_id).
+pub(crate) type ReviewKey = (String, String, String, String);
+
+pub(crate) fn review_key(review: &Review) -> ReviewKey {
+    (
+        review.store.as_str().to_string(),
+        review.app_id.clone(),
+        review.country.clone(),
+        review.review_id.clone(),
+    )
+}
+
+/// Path layout shared by the file sinks: `<output_dir>/<store>/<app_id>.<extension>`.
+pub fn output_path(output_dir: &Path, store: Store, app_id: &str, extension: &str) -> PathBuf {
+    output_dir
+        .join(store.as_str())
+        .join(format!("{app_id}.{extension}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sink_config_from_json() {
+        let sinks: Vec<SinkConfig> = serde_json::from_str(
+            r#"[
+                {"type": "csv"},
+                {"type": "ndjson"},
+                {"type": "ndjson", "gzip": true},
+                {"type": "parquet"},
+                {"type": "sqlite"},
+                {"type": "sqlite", "path": "data/reviews.db"},
+                {"type": "postgres", "url": "postgres://crawler@db/reviews"}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            sinks,
+            vec![
+                SinkConfig::Csv,
+                SinkConfig::Ndjson { gzip: false },
+                SinkConfig::Ndjson { gzip: true },
+                SinkConfig::Parquet,
+                SinkConfig::Sqlite { path: None },
+                SinkConfig::Sqlite {
+                    path: Some(PathBuf::from("data/reviews.db"))
+                },
+                SinkConfig::Postgres {
+                    url: Some("postgres://crawler@db/reviews".to_string())
+                },
+            ]
+        );
+
+        let result: Result<Vec<SinkConfig>, _> = serde_json::from_str(r#"[{"type": "ftp"}]"#);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_output_path() {
+        let path = output_path(Path::new("output"), Store::PlayStore, "com.whatsapp", "csv");
+        assert_eq!(path, Path::new("output/play_store/com.whatsapp.csv"));
+    }
+}
@@ -0,0 +1,332 @@
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Read, Seek, Write},
+    path::Path,
+};
+
+use async_trait::async_trait;
+use flate2::{read::MultiGzDecoder, write::GzEncoder, Compression};
+use serde::Deserialize;
+
+use crate::{
+    errors::CrawlerError,
+    response_processor::{
+        atomic::AtomicFile,
+        sink::{review_key, ReviewKey},
+        traits::TSink,
+    },
+    review::Review,
+};
+
+/// JSON Lines output: one [`Review`] object per line, nested fields such as
+/// the developer reply included. Like the CSV sink it appends after the
+/// reviews already in the file, skips the ones already there and replaces
+/// the file atomically when finished. With `gzip` the new reviews are added
+/// as another gzip member, which gzip readers decode as one stream; a run
+/// without new reviews leaves the gzipped file untouched.
+pub struct NdjsonSink {
+    writer: Output,
+    // 대상 파일이 이미 있었는지 (gzip에서 새 리뷰가 없으면 다시 쓰지 않음)
+    existed: bool,
+    seen: HashSet<ReviewKey>,
+    written: usize,
+    skipped: usize,
+}
+
+enum Output {
+    Plain(BufWriter<AtomicFile>),
+    Gzip(GzEncoder<BufWriter<AtomicFile>>),
+}
+
+// 기존 파일에서 중복 확인에 필요한 필드만 읽음
+#[derive(Deserialize)]
+struct KeyFields {
+    store: String,
+    app_id: String,
+    country: String,
+    review_id: String,
+}
+
+impl NdjsonSink {
+    /// Starts a new version of `path` holding the reviews already in it.
+    pub fn append(path: impl AsRef<Path>, gzip: bool) -> Result<Self, CrawlerError> {
+        let path = path.as_ref();
+        let mut file = AtomicFile::create(path)?;
+        let io_error = |e: io::Error| CrawlerError::io(path, e);
+
+        // 기존 파일은 한 줄씩 읽어 키만 모으고, 내용은 메모리에 올리지 않고 복사
+        let mut existed = true;
+        let seen = match File::open(path) {
+            Ok(mut existing) if gzip => {
+                let decoder = MultiGzDecoder::new(BufReader::new(&existing));
+                let (seen, _) = read_existing_keys(BufReader::new(decoder), path, |e| {
+                    CrawlerError::parse(
+                        None,
+                        b"",
+                        format!("Failed to decompress {}: {e}", path.display()),
+                    )
+                })?;
+                // 압축된 기존 내용은 그대로 두고 뒤에 새 gzip member를 붙임
+                existing
+                    .rewind()
+                    .and_then(|_| io::copy(&mut existing, &mut file))
+                    .map_err(io_error)?;
+                seen
+            }
+            Ok(mut existing) => {
+                let (seen, complete_len) =
+                    read_existing_keys(BufReader::new(&existing), path, io_error)?;
+                existing
+                    .rewind()
+                    .and_then(|_| io::copy(&mut (&existing).take(complete_len), &mut file))
+                    .map_err(io_error)?;
+                seen
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                existed = false;
+                HashSet::new()
+            }
+            Err(e) => return Err(CrawlerError::io(path, e)),
+        };
+
+        let writer = if gzip {
+            Output::Gzip(GzEncoder::new(BufWriter::new(file), Compression::default()))
+        } else {
+            Output::Plain(BufWriter::new(file))
+        };
+        Ok(Self {
+            writer,
+            existed,
+            seen,
+            written: 0,
+            skipped: 0,
+        })
+    }
+}
+
+#[async_trait]
+impl TSink for NdjsonSink {
+    async fn write(&mut self, data: &[Review]) -> Result<(), CrawlerError> {
+        for review in data {
+            if !self.seen.insert(review_key(review)) {
+                self.skipped += 1;
+                continue;
+            }
+            let mut line =
+                serde_json::to_vec(review).map_err(|e| CrawlerError::storage("ndjson", e))?;
+            line.push(b'\n');
+            let (result, path) = match &mut self.writer {
+                Output::Plain(writer) => (writer.write_all(&line), writer.get_ref().path()),
+                Output::Gzip(writer) => {
+                    (writer.write_all(&line), writer.get_ref().get_ref().path())
+                }
+            };
+            result.map_err(|e| CrawlerError::io(path, e))?;
+            self.written += 1;
+        }
+        Ok(())
+    }
+
+    async fn finish(self: Box<Self>) -> Result<usize, CrawlerError> {
+        let writer = match self.writer {
+            Output::Plain(writer) => writer,
+            // 빈 gzip member가 실행마다 쌓이지 않도록 임시 파일은 버리고 기존 파일을 그대로 둠
+            Output::Gzip(writer) if self.written == 0 && self.existed => {
+                tracing::debug!(
+                    "No new records for {} ({} already present)",
+                    writer.get_ref().get_ref().path().display(),
+                    self.skipped
+                );
+                return Ok(0);
+            }
+            Output::Gzip(writer) => {
+                let path = writer.get_ref().get_ref().path().to_path_buf();
+                writer.finish().map_err(|e| CrawlerError::io(&path, e))?
+            }
+        };
+        let path = writer.get_ref().path().to_path_buf();
+        let file = writer
+            .into_inner()
+            .map_err(|e| CrawlerError::io(&path, e.into_error()))?;
+        tracing::debug!(
+            "Wrote {} records to {} ({} already present)",
+            self.written,
+            file.path().display(),
+            self.skipped
+        );
+        file.commit()?;
+        Ok(self.written)
+    }
+}
+
+/// Reads the keys of the reviews already in the file, and the length up to
+/// its last complete line (a line cut off by an interrupted run is left out).
+/// `read_error` maps a failure to read `reader`.
+fn read_existing_keys(
+    mut reader: impl BufRead,
+    path: &Path,
+    read_error: impl Fn(io::Error) -> CrawlerError,
+) -> Result<(HashSet<ReviewKey>, u64), CrawlerError> {
+    let mut keys = HashSet::new();
+    let mut complete_len = 0;
+    let mut line = Vec::new();
+    for number in 1.. {
+        line.clear();
+        let read = reader.read_until(b'\n', &mut line).map_err(&read_error)?;
+        if read == 0 {
+            break;
+        }
+        if line.last() != Some(&b'\n') {
+            tracing::warn!(
+                "Dropping incomplete last line of {} (interrupted run?)",
+                path.display()
+            );
+            break;
+        }
+        complete_len += read as u64;
+        if line.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+        let fields: KeyFields = serde_json::from_slice(&line).map_err(|e| {
+            CrawlerError::parse(
+                None,
+                &line,
+                format!("Malformed line {number} in {}: {e}", path.display()),
+            )
+        })?;
+        keys.insert((
+            fields.store,
+            fields.app_id,
+            fields.country,
+            fields.review_id,
+        ));
+    }
+    Ok((keys, complete_len))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::review::DeveloperReply;
+
+    fn lines(content: &[u8]) -> Vec<serde_json::Value> {
+        String::from_utf8_lossy(content)
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_writes_one_object_per_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("reviews.jsonl");
+        let review = |id| Review {
+            developer_reply: Some(DeveloperReply {
+                body: "Sorry about that!".to_string(),
+                date: None,
+            }),
+            ..Review::sample(id)
+        };
+
+        let mut sink = NdjsonSink::append(&path, false).unwrap();
+        sink.write(&[review("1"), review("2")]).await.unwrap();
+        Box::new(sink).finish().await.unwrap();
+
+        let lines = lines(&fs::read(&path).unwrap());
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["review_id"], "1");
+        assert_eq!(lines[0]["store"], "play_store");
+        assert_eq!(lines[0]["date"], "2025-05-10T18:00:00Z");
+        assert_eq!(lines[0]["developer_reply"]["body"], "Sorry about that!");
+        // 한 줄이 Review로 다시 읽혀야 함
+        let parsed: Review = serde_json::from_value(lines[1].clone()).unwrap();
+        assert_eq!(parsed, review("2"));
+    }
+
+    #[tokio::test]
+    async fn test_append_skips_reviews_already_in_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("reviews.jsonl");
+
+        let mut sink = NdjsonSink::append(&path, false).unwrap();
+        sink.write(&[Review::sample("1")]).await.unwrap();
+        Box::new(sink).finish().await.unwrap();
+
+        // 중단된 실행이 남긴 잘린 줄은 버림
+        let mut content = fs::read(&path).unwrap();
+        content.extend_from_slice(br#"{"store":"play_store","app_id":"#);
+        fs::write(&path, content).unwrap();
+
+        let mut sink = NdjsonSink::append(&path, false).unwrap();
+        sink.write(&[Review::sample("1"), Review::sample("2")])
+            .await
+            .unwrap();
+        assert_eq!(Box::new(sink).finish().await.unwrap(), 1);
+
+        let ids: Vec<_> = lines(&fs::read(&path).unwrap())
+            .into_iter()
+            .map(|line| line["review_id"].clone())
+            .collect();
+        assert_eq!(ids, ["1", "2"]);
+    }
+
+    #[tokio::test]
+    async fn test_gzip_appends_new_member() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("reviews.jsonl.gz");
+
+        let mut sink = NdjsonSink::append(&path, true).unwrap();
+        sink.write(&[Review::sample("1")]).await.unwrap();
+        Box::new(sink).finish().await.unwrap();
+
+        let mut sink = NdjsonSink::append(&path, true).unwrap();
+        sink.write(&[Review::sample("1"), Review::sample("2")])
+            .await
+            .unwrap();
+        assert_eq!(Box::new(sink).finish().await.unwrap(), 1);
+
+        let mut content = Vec::new();
+        MultiGzDecoder::new(fs::File::open(&path).unwrap())
+            .read_to_end(&mut content)
+            .unwrap();
+        let ids: Vec<_> = lines(&content)
+            .into_iter()
+            .map(|line| line["review_id"].clone())
+            .collect();
+        assert_eq!(ids, ["1", "2"]);
+    }
+
+    #[tokio::test]
+    async fn test_gzip_run_without_new_reviews_leaves_file_untouched() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("reviews.jsonl.gz");
+
+        let mut sink = NdjsonSink::append(&path, true).unwrap();
+        sink.write(&[Review::sample("1")]).await.unwrap();
+        Box::new(sink).finish().await.unwrap();
+        let before = fs::read(&path).unwrap();
+
+        let mut sink = NdjsonSink::append(&path, true).unwrap();
+        sink.write(&[Review::sample("1")]).await.unwrap();
+        assert_eq!(Box::new(sink).finish().await.unwrap(), 0);
+
+        assert_eq!(fs::read(&path).unwrap(), before);
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_corrupt_gzip_file_is_a_parse_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("reviews.jsonl.gz");
+        fs::write(&path, b"not gzip").unwrap();
+
+        assert!(matches!(
+            NdjsonSink::append(&path, true),
+            Err(CrawlerError::Parse { .. })
+        ));
+        assert_eq!(fs::read(&path).unwrap(), b"not gzip");
+    }
+}
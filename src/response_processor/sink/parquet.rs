@@ -0,0 +1,323 @@
+use std::{
+    collections::HashSet,
+    io,
+    path::{Path, PathBuf},
+    sync::{Arc, OnceLock},
+};
+
+use arrow_array::{
+    builder::{Int32Builder, Int64Builder, StringBuilder, TimestampMicrosecondBuilder},
+    ArrayRef, RecordBatch,
+};
+use arrow_schema::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use parquet::{arrow::ArrowWriter, basic::Compression, file::properties::WriterProperties};
+
+use crate::{
+    errors::CrawlerError,
+    response_processor::{
+        atomic::AtomicFile,
+        sink::{review_key, ReviewKey},
+        traits::TSink,
+    },
+    review::{Review, Store},
+};
+
+/// Typed Parquet output for analytics. Each crawl of an app writes one file
+/// under a Hive-style partition:
+/// `<output_dir>/parquet/store=<store>/app_id=<app_id>/crawl_date=<YYYY-MM-DD>/`.
+/// The partition values are not repeated as columns; Spark, pandas and
+/// DuckDB restore them from the path when the directory is read as a dataset.
+pub struct ParquetSink {
+    path: PathBuf,
+    // 리뷰가 하나도 없으면 파일을 만들지 않음
+    writer: Option<ArrowWriter<AtomicFile>>,
+    seen: HashSet<ReviewKey>,
+    written: usize,
+}
+
+/// Columns of every Parquet file. Dates are UTC timestamps in microseconds.
+pub fn schema() -> SchemaRef {
+    static SCHEMA: OnceLock<SchemaRef> = OnceLock::new();
+    SCHEMA
+        .get_or_init(|| {
+            let timestamp = DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()));
+            Arc::new(Schema::new(vec![
+                Field::new("country", DataType::Utf8, false),
+                Field::new("review_id", DataType::Utf8, false),
+                Field::new("date", timestamp.clone(), false),
+                Field::new("rating", DataType::Int32, false),
+                Field::new("helpful_votes", DataType::Int64, false),
+                Field::new("total_votes", DataType::Int64, true),
+                Field::new("author", DataType::Utf8, false),
+                Field::new("author_uri", DataType::Utf8, true),
+                Field::new("version", DataType::Utf8, true),
+                Field::new("language", DataType::Utf8, true),
+                Field::new("title", DataType::Utf8, true),
+                Field::new("body", DataType::Utf8, false),
+                Field::new("reply_body", DataType::Utf8, true),
+                Field::new("reply_date", timestamp, true),
+            ]))
+        })
+        .clone()
+}
+
+/// `<output_dir>/parquet/store=<store>/app_id=<app_id>/crawl_date=<date>`
+pub fn partition_dir(
+    output_dir: &Path,
+    store: Store,
+    app_id: &str,
+    crawl_date: NaiveDate,
+) -> PathBuf {
+    output_dir
+        .join("parquet")
+        .join(format!("store={}", store.as_str()))
+        .join(format!("app_id={app_id}"))
+        .join(format!("crawl_date={}", crawl_date.format("%Y-%m-%d")))
+}
+
+impl ParquetSink {
+    /// Starts the file for a crawl beginning at `started_at`. Its name holds
+    /// the country and start time, so later crawls on the same day add files
+    /// next to it instead of replacing it.
+    pub fn create(
+        output_dir: &Path,
+        store: Store,
+        app_id: &str,
+        country: &str,
+        started_at: DateTime<Utc>,
+    ) -> Self {
+        let path = partition_dir(output_dir, store, app_id, started_at.date_naive()).join(format!(
+            "{country}_{}.parquet",
+            started_at.format("%Y%m%dT%H%M%S%.6fZ")
+        ));
+        Self {
+            path,
+            writer: None,
+            seen: HashSet::new(),
+            written: 0,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn writer(&mut self) -> Result<&mut ArrowWriter<AtomicFile>, CrawlerError> {
+        if self.writer.is_none() {
+            let file = AtomicFile::create(&self.path)?;
+            let properties = WriterProperties::builder()
+                .set_compression(Compression::SNAPPY)
+                .build();
+            let writer = ArrowWriter::try_new(file, schema(), Some(properties))
+                .map_err(|e| parquet_error(&self.path, e))?;
+            self.writer = Some(writer);
+        }
+        Ok(self.writer.as_mut().expect("writer was just created"))
+    }
+}
+
+#[async_trait]
+impl TSink for ParquetSink {
+    async fn write(&mut self, data: &[Review]) -> Result<(), CrawlerError> {
+        // 페이지가 겹쳐 같은 리뷰가 다시 오면 한 번만 씀
+        let new: Vec<&Review> = data
+            .iter()
+            .filter(|review| self.seen.insert(review_key(review)))
+            .collect();
+        if new.is_empty() {
+            return Ok(());
+        }
+
+        let batch = record_batch(&new).map_err(|e| CrawlerError::storage("parquet", e))?;
+        let path = self.path.clone();
+        self.writer()?
+            .write(&batch)
+            .map_err(|e| parquet_error(&path, e))?;
+        self.written += new.len();
+        Ok(())
+    }
+
+    async fn finish(self: Box<Self>) -> Result<usize, CrawlerError> {
+        let Some(writer) = self.writer else {
+            return Ok(0);
+        };
+        let file = writer
+            .into_inner()
+            .map_err(|e| parquet_error(&self.path, e))?;
+        tracing::debug!("Wrote {} records to {}", self.written, self.path.display());
+        file.commit()?;
+        Ok(self.written)
+    }
+}
+
+fn record_batch(reviews: &[&Review]) -> Result<RecordBatch, arrow_schema::ArrowError> {
+    let capacity = reviews.len();
+    let mut country = StringBuilder::new();
+    let mut review_id = StringBuilder::new();
+    let mut date = TimestampMicrosecondBuilder::with_capacity(capacity).with_timezone("UTC");
+    let mut rating = Int32Builder::with_capacity(capacity);
+    let mut helpful_votes = Int64Builder::with_capacity(capacity);
+    let mut total_votes = Int64Builder::with_capacity(capacity);
+    let mut author = StringBuilder::new();
+    let mut author_uri = StringBuilder::new();
+    let mut version = StringBuilder::new();
+    let mut language = StringBuilder::new();
+    let mut title = StringBuilder::new();
+    let mut body = StringBuilder::new();
+    let mut reply_body = StringBuilder::new();
+    let mut reply_date = TimestampMicrosecondBuilder::with_capacity(capacity).with_timezone("UTC");
+
+    for review in reviews {
+        let reply = review.developer_reply.as_ref();
+        country.append_value(&review.country);
+        review_id.append_value(&review.review_id);
+        date.append_value(review.date.timestamp_micros());
+        rating.append_value(review.rating.into());
+        helpful_votes.append_value(review.helpful_votes.into());
+        total_votes.append_option(review.total_votes.map(i64::from));
+        author.append_value(&review.author);
+        author_uri.append_option(review.author_uri.as_deref());
+        version.append_option(review.version.as_deref());
+        language.append_option(review.language.as_deref());
+        title.append_option(review.title.as_deref());
+        body.append_value(&review.body);
+        reply_body.append_option(reply.map(|reply| reply.body.as_str()));
+        reply_date.append_option(
+            reply
+                .and_then(|reply| reply.date)
+                .map(|date| date.timestamp_micros()),
+        );
+    }
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(country.finish()),
+        Arc::new(review_id.finish()),
+        Arc::new(date.finish()),
+        Arc::new(rating.finish()),
+        Arc::new(helpful_votes.finish()),
+        Arc::new(total_votes.finish()),
+        Arc::new(author.finish()),
+        Arc::new(author_uri.finish()),
+        Arc::new(version.finish()),
+        Arc::new(language.finish()),
+        Arc::new(title.finish()),
+        Arc::new(body.finish()),
+        Arc::new(reply_body.finish()),
+        Arc::new(reply_date.finish()),
+    ];
+    RecordBatch::try_new(schema(), columns)
+}
+
+fn parquet_error(path: &Path, e: parquet::errors::ParquetError) -> CrawlerError {
+    match e {
+        parquet::errors::ParquetError::External(source) => match source.downcast::<io::Error>() {
+            Ok(source) => CrawlerError::io(path, *source),
+            Err(source) => CrawlerError::storage("parquet", source),
+        },
+        e => CrawlerError::storage("parquet", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use arrow_array::{
+        cast::AsArray,
+        types::{Int32Type, TimestampMicrosecondType},
+    };
+    use chrono::TimeZone;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    use super::*;
+    use crate::review::DeveloperReply;
+
+    fn sink(output_dir: &Path) -> ParquetSink {
+        let started_at = Utc.with_ymd_and_hms(2026, 10, 17, 9, 30, 0).unwrap();
+        ParquetSink::create(
+            output_dir,
+            Store::PlayStore,
+            "com.example.app",
+            "us",
+            started_at,
+        )
+    }
+
+    #[test]
+    fn test_partitioned_path() {
+        let sink = sink(Path::new("output"));
+        assert_eq!(
+            sink.path(),
+            Path::new(
+                "output/parquet/store=play_store/app_id=com.example.app/crawl_date=2026-10-17/\
+                 us_20261017T093000.000000Z.parquet"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_writes_typed_columns() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut sink = Box::new(sink(dir.path()));
+        let path = sink.path().to_path_buf();
+        let review = |id| Review {
+            date: Utc.timestamp_opt(1_746_900_000, 123_000_000).unwrap(),
+            developer_reply: Some(DeveloperReply {
+                body: "Sorry about that!".to_string(),
+                date: None,
+            }),
+            ..Review::sample(id)
+        };
+
+        sink.write(&[review("1"), review("2")]).await.unwrap();
+        // 이미 쓴 리뷰는 건너뜀
+        sink.write(&[review("2"), review("3")]).await.unwrap();
+        assert_eq!(sink.finish().await.unwrap(), 3);
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        let batches: Vec<RecordBatch> = reader.map(Result::unwrap).collect();
+        assert_eq!(batches[0].schema(), schema());
+        let rows: usize = batches.iter().map(RecordBatch::num_rows).sum();
+        assert_eq!(rows, 3);
+
+        let batch = &batches[0];
+        let ids = batch
+            .column_by_name("review_id")
+            .unwrap()
+            .as_string::<i32>();
+        assert_eq!(ids.value(0), "1");
+        let dates = batch
+            .column_by_name("date")
+            .unwrap()
+            .as_primitive::<TimestampMicrosecondType>();
+        assert_eq!(dates.value(0), 1_746_900_000_123_000);
+        let ratings = batch
+            .column_by_name("rating")
+            .unwrap()
+            .as_primitive::<Int32Type>();
+        assert_eq!(ratings.value(0), 2);
+        let total_votes = batch.column_by_name("total_votes").unwrap();
+        assert!(total_votes.is_null(0));
+        let replies = batch
+            .column_by_name("reply_body")
+            .unwrap()
+            .as_string::<i32>();
+        assert_eq!(replies.value(0), "Sorry about that!");
+    }
+
+    #[tokio::test]
+    async fn test_no_file_without_reviews() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut sink = Box::new(sink(dir.path()));
+        let path = sink.path().to_path_buf();
+
+        sink.write(&[]).await.unwrap();
+        assert_eq!(sink.finish().await.unwrap(), 0);
+        assert!(!path.exists());
+    }
+}
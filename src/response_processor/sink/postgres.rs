@@ -0,0 +1,431 @@
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use postgres_native_tls::MakeTlsConnector;
+use tokio::sync::Mutex;
+use tokio_postgres::{
+    config::SslMode,
+    tls::{MakeTlsConnect, TlsConnect},
+    types::ToSql,
+    Client, Config, NoTls, Socket,
+};
+
+use crate::{
+    errors::CrawlerError,
+    response_processor::{
+        sink::{review_key, ReviewKey},
+        traits::TSink,
+    },
+    review::{Review, Store},
+};
+
+/// Schema versions, applied in order. A database records the versions it
+/// already has in `schema_migrations`, so only the newer ones run. Append new
+/// versions; never edit one that has shipped.
+const MIGRATIONS: &[(i32, &str)] = &[(
+    1,
+    "
+    CREATE TABLE reviews (
+        store         TEXT        NOT NULL,
+        app_id        TEXT        NOT NULL,
+        country       TEXT        NOT NULL,
+        review_id     TEXT        NOT NULL,
+        author        TEXT        NOT NULL,
+        author_uri    TEXT,
+        date          TIMESTAMPTZ NOT NULL,
+        rating        SMALLINT    NOT NULL,
+        helpful_votes BIGINT      NOT NULL,
+        total_votes   BIGINT,
+        version       TEXT,
+        language      TEXT,
+        title         TEXT,
+        body          TEXT        NOT NULL,
+        reply_body    TEXT,
+        reply_date    TIMESTAMPTZ,
+        first_seen_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+        updated_at    TIMESTAMPTZ NOT NULL DEFAULT now(),
+        PRIMARY KEY (store, app_id, country, review_id)
+    );
+
+    CREATE TABLE crawl_runs (
+        id              BIGSERIAL   PRIMARY KEY,
+        store           TEXT        NOT NULL,
+        app_id          TEXT        NOT NULL,
+        country         TEXT        NOT NULL,
+        started_at      TIMESTAMPTZ NOT NULL DEFAULT now(),
+        finished_at     TIMESTAMPTZ,
+        pages           INTEGER     NOT NULL DEFAULT 0,
+        reviews_fetched INTEGER     NOT NULL DEFAULT 0,
+        reviews_new     INTEGER     NOT NULL DEFAULT 0
+    );
+    ",
+)];
+
+// 동시에 열리는 싱크끼리 마이그레이션이 겹치지 않도록 잡는 advisory lock 키
+const MIGRATION_LOCK: i64 = 0x6170_7072_6576;
+
+const REVIEW_COLUMNS: [&str; 16] = [
+    "store",
+    "app_id",
+    "country",
+    "review_id",
+    "author",
+    "author_uri",
+    "date",
+    "rating",
+    "helpful_votes",
+    "total_votes",
+    "version",
+    "language",
+    "title",
+    "body",
+    "reply_body",
+    "reply_date",
+];
+
+// PostgreSQL은 문장 하나에 65535개까지 파라미터를 받음
+const BATCH_ROWS: usize = 1000;
+
+/// One PostgreSQL connection for a whole run. [`PostgresDatabase::connect`]
+/// migrates the schema once; every app's [`PostgresSink`] then shares the
+/// connection, one page transaction at a time.
+#[derive(Clone)]
+pub struct PostgresDatabase {
+    client: Arc<Mutex<Client>>,
+}
+
+impl fmt::Debug for PostgresDatabase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PostgresDatabase").finish_non_exhaustive()
+    }
+}
+
+impl PostgresDatabase {
+    /// Connects to `url` and migrates the schema. TLS follows the URL's
+    /// `sslmode`: `disable` connects in plain text, `prefer` (the default)
+    /// falls back to plain text when the server has no TLS, and `require`
+    /// fails without it. Server certificates are checked against the system
+    /// trust store.
+    pub async fn connect(url: &str) -> Result<Self, CrawlerError> {
+        let config: Config = url
+            .parse()
+            .map_err(|e| CrawlerError::config(Some("sinks.url"), e))?;
+        let mut client = match config.get_ssl_mode() {
+            SslMode::Disable => connect_with(&config, NoTls).await?,
+            _ => {
+                let connector = native_tls::TlsConnector::new()
+                    .map_err(|e| CrawlerError::storage("PostgreSQL", e))?;
+                connect_with(&config, MakeTlsConnector::new(connector)).await?
+            }
+        };
+
+        migrate(&mut client).await?;
+
+        Ok(Self {
+            client: Arc::new(Mutex::new(client)),
+        })
+    }
+
+    /// Starts a crawl run for one app.
+    pub async fn sink(
+        &self,
+        store: Store,
+        app_id: &str,
+        country: &str,
+    ) -> Result<PostgresSink, CrawlerError> {
+        let row = self
+            .client
+            .lock()
+            .await
+            .query_one(
+                "INSERT INTO crawl_runs (store, app_id, country) VALUES ($1, $2, $3) RETURNING id",
+                &[&store.as_str(), &app_id, &country],
+            )
+            .await
+            .map_err(db_error)?;
+
+        Ok(PostgresSink {
+            database: self.clone(),
+            run_id: row.get(0),
+            pages: 0,
+            fetched: 0,
+            inserted: 0,
+        })
+    }
+}
+
+/// Shared PostgreSQL storage. Like the SQLite sink, reviews are upserted by
+/// (store, app_id, country, review_id) and every crawl adds a `crawl_runs`
+/// row; pages are sent as multi-row `INSERT ... ON CONFLICT` statements.
+pub struct PostgresSink {
+    database: PostgresDatabase,
+    run_id: i64,
+    pages: usize,
+    fetched: usize,
+    inserted: usize,
+}
+
+impl PostgresSink {
+    /// Connects to `url` and starts a crawl run. A run over several apps
+    /// should connect once with [`PostgresDatabase::connect`] instead.
+    pub async fn connect(
+        url: &str,
+        store: Store,
+        app_id: &str,
+        country: &str,
+    ) -> Result<Self, CrawlerError> {
+        PostgresDatabase::connect(url)
+            .await?
+            .sink(store, app_id, country)
+            .await
+    }
+}
+
+#[async_trait]
+impl TSink for PostgresSink {
+    /// Upserts the page in one transaction.
+    async fn write(&mut self, data: &[Review]) -> Result<(), CrawlerError> {
+        // 빈 페이지는 트랜잭션을 열지 않고 crawl_runs.pages에도 세지 않음
+        if data.is_empty() {
+            return Ok(());
+        }
+        // 한 문장 안에서 같은 키를 두 번 갱신할 수 없으므로 마지막 것만 남김
+        let mut latest: HashMap<ReviewKey, usize> = HashMap::new();
+        for (i, review) in data.iter().enumerate() {
+            latest.insert(review_key(review), i);
+        }
+        let reviews: Vec<&Review> = data
+            .iter()
+            .enumerate()
+            .filter(|(i, review)| latest[&review_key(review)] == *i)
+            .map(|(_, review)| review)
+            .collect();
+
+        // 연결을 공유하므로 트랜잭션이 끝날 때까지 다른 앱의 쓰기는 기다림
+        let mut client = self.database.client.lock().await;
+        let transaction = client.transaction().await.map_err(db_error)?;
+        let mut inserted = 0;
+        for batch in reviews.chunks(BATCH_ROWS) {
+            let rows = ReviewRow::from_reviews(batch);
+            let params: Vec<&(dyn ToSql + Sync)> =
+                rows.iter().flat_map(ReviewRow::params).collect();
+            let result = transaction
+                .query(&upsert_statement(rows.len()), &params)
+                .await
+                .map_err(db_error)?;
+            inserted += result
+                .iter()
+                .filter(|row| row.get::<_, bool>("inserted"))
+                .count();
+        }
+        transaction.commit().await.map_err(db_error)?;
+
+        self.pages += 1;
+        self.fetched += data.len();
+        self.inserted += inserted;
+        Ok(())
+    }
+
+    async fn finish(self: Box<Self>) -> Result<usize, CrawlerError> {
+        self.database
+            .client
+            .lock()
+            .await
+            .execute(
+                "UPDATE crawl_runs
+                 SET finished_at = now(), pages = $1, reviews_fetched = $2, reviews_new = $3
+                 WHERE id = $4",
+                &[
+                    &(self.pages as i32),
+                    &(self.fetched as i32),
+                    &(self.inserted as i32),
+                    &self.run_id,
+                ],
+            )
+            .await
+            .map_err(db_error)?;
+        tracing::debug!(
+            "Upserted {} reviews into PostgreSQL ({} new)",
+            self.fetched,
+            self.inserted
+        );
+        Ok(self.inserted)
+    }
+}
+
+async fn connect_with<T>(config: &Config, tls: T) -> Result<Client, CrawlerError>
+where
+    T: MakeTlsConnect<Socket>,
+    T::Stream: Send + 'static,
+    T::TlsConnect: Send,
+    <T::TlsConnect as TlsConnect<Socket>>::Future: Send,
+{
+    let (client, connection) = config.connect(tls).await.map_err(db_error)?;
+    tokio::spawn(async move {
+        if let Err(e) = connection.await {
+            tracing::error!("PostgreSQL connection error: {}", e);
+        }
+    });
+    Ok(client)
+}
+
+/// Applies the migrations the database does not have yet, in one
+/// transaction, under an advisory lock so concurrent crawls wait for the
+/// first one instead of racing it.
+async fn migrate(client: &mut Client) -> Result<(), CrawlerError> {
+    let transaction = client.transaction().await.map_err(db_error)?;
+    transaction
+        .execute("SELECT pg_advisory_xact_lock($1)", &[&MIGRATION_LOCK])
+        .await
+        .map_err(db_error)?;
+    transaction
+        .batch_execute(
+            "CREATE TABLE IF NOT EXISTS schema_migrations (
+                version    INTEGER     PRIMARY KEY,
+                applied_at TIMESTAMPTZ NOT NULL DEFAULT now()
+            )",
+        )
+        .await
+        .map_err(db_error)?;
+
+    let current: i32 = transaction
+        .query_one(
+            "SELECT COALESCE(MAX(version), 0) FROM schema_migrations",
+            &[],
+        )
+        .await
+        .map_err(db_error)?
+        .get(0);
+    for (version, sql) in MIGRATIONS.iter().filter(|(version, _)| *version > current) {
+        tracing::info!("Applying PostgreSQL schema migration {}", version);
+        transaction.batch_execute(sql).await.map_err(db_error)?;
+        transaction
+            .execute(
+                "INSERT INTO schema_migrations (version) VALUES ($1)",
+                &[version],
+            )
+            .await
+            .map_err(db_error)?;
+    }
+    transaction.commit().await.map_err(db_error)
+}
+
+/// `INSERT ... VALUES (...), (...) ON CONFLICT DO UPDATE` for `rows` reviews.
+/// Each returned row tells whether the review was new (`xmax = 0` only for
+/// freshly inserted tuples).
+fn upsert_statement(rows: usize) -> String {
+    let width = REVIEW_COLUMNS.len();
+    let values: Vec<String> = (0..rows)
+        .map(|row| {
+            let placeholders: Vec<String> = (1..=width)
+                .map(|column| format!("${}", row * width + column))
+                .collect();
+            format!("({})", placeholders.join(", "))
+        })
+        .collect();
+    let updates: Vec<String> = REVIEW_COLUMNS[4..]
+        .iter()
+        .map(|column| format!("{column} = EXCLUDED.{column}"))
+        .collect();
+
+    format!(
+        "INSERT INTO reviews ({}) VALUES {} \
+         ON CONFLICT (store, app_id, country, review_id) DO UPDATE SET {}, updated_at = now() \
+         RETURNING (xmax = 0) AS inserted",
+        REVIEW_COLUMNS.join(", "),
+        values.join(", "),
+        updates.join(", ")
+    )
+}
+
+// Review 필드를 PostgreSQL 컬럼 타입으로 바꾼 한 행
+struct ReviewRow<'a> {
+    store: &'static str,
+    review: &'a Review,
+    rating: i16,
+    helpful_votes: i64,
+    total_votes: Option<i64>,
+    reply_body: Option<&'a str>,
+    reply_date: Option<DateTime<Utc>>,
+}
+
+impl<'a> ReviewRow<'a> {
+    fn from_reviews(reviews: &[&'a Review]) -> Vec<Self> {
+        reviews
+            .iter()
+            .map(|review| {
+                let reply = review.developer_reply.as_ref();
+                ReviewRow {
+                    store: review.store.as_str(),
+                    review,
+                    rating: review.rating.into(),
+                    helpful_votes: review.helpful_votes.into(),
+                    total_votes: review.total_votes.map(i64::from),
+                    reply_body: reply.map(|reply| reply.body.as_str()),
+                    reply_date: reply.and_then(|reply| reply.date),
+                }
+            })
+            .collect()
+    }
+
+    /// Parameters in [`REVIEW_COLUMNS`] order.
+    fn params(&self) -> [&(dyn ToSql + Sync); REVIEW_COLUMNS.len()] {
+        [
+            &self.store,
+            &self.review.app_id,
+            &self.review.country,
+            &self.review.review_id,
+            &self.review.author,
+            &self.review.author_uri,
+            &self.review.date,
+            &self.rating,
+            &self.helpful_votes,
+            &self.total_votes,
+            &self.review.version,
+            &self.review.language,
+            &self.review.title,
+            &self.review.body,
+            &self.reply_body,
+            &self.reply_date,
+        ]
+    }
+}
+
+fn db_error(e: tokio_postgres::Error) -> CrawlerError {
+    CrawlerError::storage("PostgreSQL", e)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upsert_statement_numbers_placeholders_per_row() {
+        let sql = upsert_statement(2);
+        assert!(sql.starts_with(
+            "INSERT INTO reviews (store, app_id, country, review_id, author, author_uri, date, \
+             rating, helpful_votes, total_votes, version, language, title, body, reply_body, \
+             reply_date) VALUES ($1, $2, $3,"
+        ));
+        assert!(sql.contains("$16), ($17, $18,"));
+        assert!(sql.contains("$32) ON CONFLICT (store, app_id, country, review_id)"));
+        assert!(!sql.contains("$33"));
+        // 키 컬럼은 갱신하지 않음
+        assert!(sql.contains("DO UPDATE SET author = EXCLUDED.author,"));
+        assert!(!sql.contains("store = EXCLUDED.store"));
+        assert!(sql.ends_with("updated_at = now() RETURNING (xmax = 0) AS inserted"));
+    }
+
+    #[test]
+    fn test_batch_fits_parameter_limit() {
+        assert!(BATCH_ROWS * REVIEW_COLUMNS.len() <= u16::MAX as usize);
+    }
+
+    #[test]
+    fn test_migration_versions_increase() {
+        let versions: Vec<i32> = MIGRATIONS.iter().map(|(version, _)| *version).collect();
+        assert!(versions.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(versions.first(), Some(&1));
+    }
+}
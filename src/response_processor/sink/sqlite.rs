@@ -0,0 +1,282 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, PoisonError},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{params, Connection, OptionalExtension, TransactionBehavior};
+
+use crate::{
+    errors::CrawlerError,
+    response_processor::traits::TSink,
+    review::{Review, Store},
+};
+
+// 여러 앱이 같은 DB 파일에 동시에 쓰므로 잠금이 풀릴 때까지 기다림
+const BUSY_TIMEOUT: Duration = Duration::from_secs(30);
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS reviews (
+    store         TEXT    NOT NULL,
+    app_id        TEXT    NOT NULL,
+    country       TEXT    NOT NULL,
+    review_id     TEXT    NOT NULL,
+    author        TEXT    NOT NULL,
+    author_uri    TEXT,
+    date          TEXT    NOT NULL,
+    rating        INTEGER NOT NULL,
+    helpful_votes INTEGER NOT NULL,
+    total_votes   INTEGER,
+    version       TEXT,
+    language      TEXT,
+    title         TEXT,
+    body          TEXT    NOT NULL,
+    reply_body    TEXT,
+    reply_date    TEXT,
+    first_seen_at TEXT    NOT NULL,
+    updated_at    TEXT    NOT NULL,
+    PRIMARY KEY (store, app_id, country, review_id)
+);
+
+CREATE TABLE IF NOT EXISTS crawl_runs (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    store           TEXT    NOT NULL,
+    app_id          TEXT    NOT NULL,
+    country         TEXT    NOT NULL,
+    started_at      TEXT    NOT NULL,
+    finished_at     TEXT,
+    pages           INTEGER NOT NULL DEFAULT 0,
+    reviews_fetched INTEGER NOT NULL DEFAULT 0,
+    reviews_new     INTEGER NOT NULL DEFAULT 0
+);
+";
+
+const UPSERT_REVIEW: &str = "
+INSERT INTO reviews (
+    store, app_id, country, review_id, author, author_uri, date, rating,
+    helpful_votes, total_votes, version, language, title, body, reply_body,
+    reply_date, first_seen_at, updated_at
+) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?17)
+ON CONFLICT (store, app_id, country, review_id) DO UPDATE SET
+    author        = excluded.author,
+    author_uri    = excluded.author_uri,
+    date          = excluded.date,
+    rating        = excluded.rating,
+    helpful_votes = excluded.helpful_votes,
+    total_votes   = excluded.total_votes,
+    version       = excluded.version,
+    language      = excluded.language,
+    title         = excluded.title,
+    body          = excluded.body,
+    reply_body    = excluded.reply_body,
+    reply_date    = excluded.reply_date,
+    updated_at    = excluded.updated_at
+";
+
+/// Embedded SQLite storage. Reviews are upserted into `reviews`, keyed by
+/// (store, app_id, country, review_id), so an edited review replaces the
+/// stored one. Every crawl of an app adds a row to `crawl_runs`; a run whose
+/// `finished_at` is empty was interrupted.
+///
+/// rusqlite is synchronous and a locked database is waited on for up to
+/// [`BUSY_TIMEOUT`], so every call runs on tokio's blocking thread pool.
+pub struct SqliteSink {
+    conn: Arc<Mutex<Connection>>,
+    run_id: i64,
+    pages: usize,
+    fetched: usize,
+    inserted: usize,
+}
+
+impl SqliteSink {
+    /// Opens (and if needed creates) the database and starts a crawl run.
+    pub async fn open(
+        path: impl Into<PathBuf>,
+        store: Store,
+        app_id: &str,
+        country: &str,
+    ) -> Result<Self, CrawlerError> {
+        let path = path.into();
+        let (app_id, country) = (app_id.to_string(), country.to_string());
+        let (conn, run_id) = blocking(move || start_run(&path, store, &app_id, &country)).await?;
+
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+            run_id,
+            pages: 0,
+            fetched: 0,
+            inserted: 0,
+        })
+    }
+}
+
+#[async_trait]
+impl TSink for SqliteSink {
+    /// Upserts the page in one transaction.
+    async fn write(&mut self, data: &[Review]) -> Result<(), CrawlerError> {
+        // 빈 페이지는 crawl_runs.pages에 세지 않음
+        if data.is_empty() {
+            return Ok(());
+        }
+        let conn = self.conn.clone();
+        let reviews = data.to_vec();
+        let inserted = blocking(move || {
+            let mut conn = conn.lock().unwrap_or_else(PoisonError::into_inner);
+            upsert(&mut conn, &reviews)
+        })
+        .await?;
+
+        self.pages += 1;
+        self.fetched += data.len();
+        self.inserted += inserted;
+        Ok(())
+    }
+
+    async fn finish(self: Box<Self>) -> Result<usize, CrawlerError> {
+        let Self {
+            conn,
+            run_id,
+            pages,
+            fetched,
+            inserted,
+        } = *self;
+        blocking(move || {
+            let conn = conn.lock().unwrap_or_else(PoisonError::into_inner);
+            conn.execute(
+                "UPDATE crawl_runs
+                 SET finished_at = ?1, pages = ?2, reviews_fetched = ?3, reviews_new = ?4
+                 WHERE id = ?5",
+                params![
+                    timestamp(Utc::now()),
+                    pages as i64,
+                    fetched as i64,
+                    inserted as i64,
+                    run_id
+                ],
+            )
+            .map_err(db_error)
+        })
+        .await?;
+        tracing::debug!(
+            "Upserted {} reviews into SQLite ({} new)",
+            fetched,
+            inserted
+        );
+        Ok(inserted)
+    }
+}
+
+/// Opens the database, creates the schema and adds a `crawl_runs` row.
+fn start_run(
+    path: &Path,
+    store: Store,
+    app_id: &str,
+    country: &str,
+) -> Result<(Connection, i64), CrawlerError> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|e| CrawlerError::io(parent, e))?;
+    }
+
+    let conn = Connection::open(path).map_err(|e| sqlite_error(path, e))?;
+    conn.busy_timeout(BUSY_TIMEOUT)
+        .and_then(|_| conn.pragma_update(None, "journal_mode", "WAL"))
+        .and_then(|_| conn.execute_batch(SCHEMA))
+        .map_err(|e| sqlite_error(path, e))?;
+
+    conn.execute(
+        "INSERT INTO crawl_runs (store, app_id, country, started_at) VALUES (?1, ?2, ?3, ?4)",
+        params![store.as_str(), app_id, country, timestamp(Utc::now())],
+    )
+    .map_err(|e| sqlite_error(path, e))?;
+    let run_id = conn.last_insert_rowid();
+    Ok((conn, run_id))
+}
+
+/// Upserts `data` in one transaction and returns how many reviews were new.
+fn upsert(conn: &mut Connection, data: &[Review]) -> Result<usize, CrawlerError> {
+    let now = timestamp(Utc::now());
+    // 읽기 후 쓰기로 잠금을 올리면 busy_timeout 없이 바로 실패하므로 처음부터 쓰기 잠금을 잡음
+    let tx = conn
+        .transaction_with_behavior(TransactionBehavior::Immediate)
+        .map_err(db_error)?;
+    let mut inserted = 0;
+    {
+        let mut exists = tx
+            .prepare_cached(
+                "SELECT 1 FROM reviews
+                 WHERE store = ?1 AND app_id = ?2 AND country = ?3 AND review_id = ?4",
+            )
+            .map_err(db_error)?;
+        let mut upsert = tx.prepare_cached(UPSERT_REVIEW).map_err(db_error)?;
+
+        for review in data {
+            let key = params![
+                review.store.as_str(),
+                review.app_id,
+                review.country,
+                review.review_id
+            ];
+            let is_new = exists
+                .query_row(key, |_| Ok(()))
+                .optional()
+                .map_err(db_error)?
+                .is_none();
+
+            let reply = review.developer_reply.as_ref();
+            upsert
+                .execute(params![
+                    review.store.as_str(),
+                    review.app_id,
+                    review.country,
+                    review.review_id,
+                    review.author,
+                    review.author_uri,
+                    timestamp(review.date),
+                    review.rating,
+                    review.helpful_votes,
+                    review.total_votes,
+                    review.version,
+                    review.language,
+                    review.title,
+                    review.body,
+                    reply.map(|reply| reply.body.as_str()),
+                    reply.and_then(|reply| reply.date).map(timestamp),
+                    now,
+                ])
+                .map_err(db_error)?;
+            if is_new {
+                inserted += 1;
+            }
+        }
+    }
+    tx.commit().map_err(db_error)?;
+    Ok(inserted)
+}
+
+// 잠긴 DB를 기다리는 동안 런타임 워커 스레드를 막지 않도록 blocking 풀에서 실행
+async fn blocking<T, F>(f: F) -> Result<T, CrawlerError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, CrawlerError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| CrawlerError::storage("SQLite", e))?
+}
+
+// CSV/JSON 출력과 같은 RFC 3339 형식으로 저장
+fn timestamp(date: DateTime<Utc>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+fn sqlite_error(path: &Path, e: rusqlite::Error) -> CrawlerError {
+    // rusqlite 오류에는 파일 경로가 없으므로 로그에 남김
+    tracing::error!("Failed to open SQLite database {}: {}", path.display(), e);
+    db_error(e)
+}
+
+fn db_error(e: rusqlite::Error) -> CrawlerError {
+    CrawlerError::storage("SQLite", e)
+}
@@ -0,0 +1,138 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::CrawlerError,
+    response_processor::atomic::write_atomic,
+    review::{Review, Store},
+};
+
+/// High-water mark of an app and country: the newest review written so far.
+/// Feeds are sorted newest first, so a crawl can stop as soon as it reaches it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrawlState {
+    pub newest_review_id: String,
+    pub newest_date: DateTime<Utc>,
+}
+
+/// Where the state of one app and country is kept, e.g.
+/// `output/.state/app_store/1194408342_us.json`.
+pub fn state_path(output_dir: &Path, store: Store, app_id: &str, country: &str) -> PathBuf {
+    output_dir
+        .join(".state")
+        .join(store.as_str())
+        .join(format!("{app_id}_{country}.json"))
+}
+
+impl CrawlState {
+    /// Returns `None` when the app has not been crawled before.
+    pub fn load(path: &Path) -> Result<Option<Self>, CrawlerError> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(path).map_err(|e| CrawlerError::io(path, e))?;
+        serde_json::from_str(&content).map(Some).map_err(|e| {
+            CrawlerError::parse(
+                None,
+                content.as_bytes(),
+                format!("Invalid state file {}: {e}", path.display()),
+            )
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), CrawlerError> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| CrawlerError::storage("state file", e))?;
+        write_atomic(path, content.as_bytes())
+    }
+
+    /// Whether the review was already written by an earlier crawl.
+    pub fn has_seen(&self, review: &Review) -> bool {
+        review.review_id == self.newest_review_id || review.date < self.newest_date
+    }
+
+    /// Moves the mark forward if `review` is newer than it.
+    pub fn advance(state: &mut Option<Self>, review: &Review) {
+        if state
+            .as_ref()
+            .is_none_or(|state| review.date > state.newest_date)
+        {
+            *state = Some(Self {
+                newest_review_id: review.review_id.clone(),
+                newest_date: review.date,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn review(id: &str, secs: i64) -> Review {
+        Review {
+            date: Utc.timestamp_opt(secs, 0).unwrap(),
+            ..Review::sample(id)
+        }
+    }
+
+    #[test]
+    fn test_state_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = state_path(dir.path(), Store::AppStore, "42", "us");
+        assert!(path.ends_with(".state/app_store/42_us.json"));
+        assert_eq!(CrawlState::load(&path).unwrap(), None);
+
+        let state = CrawlState {
+            newest_review_id: "12645174720".to_string(),
+            newest_date: Utc.timestamp_opt(1_747_000_000, 0).unwrap(),
+        };
+        state.save(&path).unwrap();
+
+        assert_eq!(CrawlState::load(&path).unwrap(), Some(state));
+    }
+
+    #[test]
+    fn test_invalid_state_file_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.json");
+        fs::write(&path, "not json").unwrap();
+
+        assert!(matches!(
+            CrawlState::load(&path),
+            Err(CrawlerError::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn test_has_seen() {
+        let state = CrawlState {
+            newest_review_id: "b".to_string(),
+            newest_date: Utc.timestamp_opt(100, 0).unwrap(),
+        };
+
+        assert!(!state.has_seen(&review("c", 101)));
+        assert!(state.has_seen(&review("b", 100)));
+        assert!(state.has_seen(&review("a", 99)));
+        // 같은 시각의 다른 리뷰는 새 리뷰로 취급
+        assert!(!state.has_seen(&review("d", 100)));
+    }
+
+    #[test]
+    fn test_advance_keeps_the_newest_review() {
+        let mut state = None;
+        CrawlState::advance(&mut state, &review("b", 100));
+        CrawlState::advance(&mut state, &review("a", 50));
+        assert_eq!(state.as_ref().unwrap().newest_review_id, "b");
+
+        CrawlState::advance(&mut state, &review("c", 150));
+        assert_eq!(state.unwrap().newest_review_id, "c");
+    }
+}
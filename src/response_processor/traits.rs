@@ -1,45 +1,44 @@
-use crate::errors::CrawlerError;
+use async_trait::async_trait;
 
-pub trait TStoreType {
-    fn get_output_path(&self, app_id: &str) -> String;
-}
+use crate::{
+    errors::CrawlerError,
+    review::{Review, Store},
+};
 
-pub trait TExtractData: serde::Serialize {
-    fn extract_data(&self, response: &[u8]) -> Result<Vec<Self>, CrawlerError>
-    where
-        Self: Sized;
+pub trait TStoreType {
+    fn store(&self) -> Store;
 }
 
-// 공통 CSV 저장 함수
-pub fn save_data_to_csv<T>(
-    data: Vec<T>,
-    store_type: &dyn TStoreType,
-    app_id: &str,
-) -> Result<(), CrawlerError>
-where
-    T: serde::Serialize,
-{
-    use std::fs;
-    use std::path::PathBuf;
-
-    // 출력 디렉토리 생성
-    let output_path = PathBuf::from(store_type.get_output_path(app_id));
-    if let Some(parent) = output_path.parent() {
-        fs::create_dir_all(parent)
-            .map_err(|e| CrawlerError::Request(format!("Failed to create directory: {e}")))?;
+pub trait TExtractData {
+    fn extract_data(&self, response: &[u8]) -> Result<Vec<Review>, CrawlerError>;
+
+    /// Like [`TExtractData::extract_data`], also counting the entries that
+    /// were skipped for missing required fields. Extractors that skip
+    /// entries override this; the default reports none skipped.
+    fn extract_page(&self, response: &[u8]) -> Result<ExtractedPage, CrawlerError> {
+        Ok(ExtractedPage {
+            reviews: self.extract_data(response)?,
+            skipped: 0,
+        })
     }
+}
 
-    // CSV 파일 작성
-    let mut wtr = csv::Writer::from_path(&output_path)
-        .map_err(|e| CrawlerError::Request(format!("Failed to create CSV file: {e}")))?;
-
-    for item in data {
-        wtr.serialize(item)
-            .map_err(|e| CrawlerError::Request(format!("Failed to serialize data: {e}")))?;
-    }
+/// Reviews extracted from one page.
+#[derive(Debug, Default)]
+pub struct ExtractedPage {
+    pub reviews: Vec<Review>,
+    /// Incomplete entries left out of `reviews`.
+    pub skipped: usize,
+}
 
-    wtr.flush()
-        .map_err(|e| CrawlerError::Request(format!("Failed to flush CSV file: {e}")))?;
+/// Destination the reviews of one app are written to.
+#[async_trait]
+pub trait TSink: Send {
+    /// Called with the reviews of each page as it is crawled.
+    async fn write(&mut self, reviews: &[Review]) -> Result<(), CrawlerError>;
 
-    Ok(())
+    /// Called once when the crawl of the app ends, also after a failed page,
+    /// so the reviews written so far are kept. Returns the number of new
+    /// reviews stored.
+    async fn finish(self: Box<Self>) -> Result<usize, CrawlerError>;
 }
@@ -0,0 +1,93 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Store a review was crawled from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Store {
+    AppStore,
+    PlayStore,
+}
+
+impl Store {
+    /// Stable identifier, also used as the output directory name.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Store::AppStore => "app_store",
+            Store::PlayStore => "play_store",
+        }
+    }
+}
+
+impl fmt::Display for Store {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One review, independent of the store it came from. Every extractor maps
+/// into this type, so sinks only have to handle a single shape.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Review {
+    pub store: Store,
+    pub app_id: String,
+    pub country: String,
+    /// The store's own review ID, stable across crawls.
+    pub review_id: String,
+    pub author: String,
+    /// Link to the author's profile (App Store only).
+    pub author_uri: Option<String>,
+    /// Normalized to UTC; serialized as RFC 3339 (`2025-05-11T17:19:38Z`).
+    pub date: DateTime<Utc>,
+    /// Star rating, 1 to 5.
+    pub rating: u8,
+    /// Users who found the review helpful.
+    pub helpful_votes: u32,
+    /// Users who voted on the review either way (App Store only).
+    pub total_votes: Option<u32>,
+    /// App version the review was written against.
+    pub version: Option<String>,
+    pub language: Option<String>,
+    /// Review title (App Store only).
+    pub title: Option<String>,
+    pub body: String,
+    pub developer_reply: Option<DeveloperReply>,
+}
+
+/// The developer's public answer to a review.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeveloperReply {
+    pub body: String,
+    pub date: Option<DateTime<Utc>>,
+}
+
+#[cfg(any(test, feature = "test-support"))]
+impl Review {
+    /// A Play Store review with placeholder values, shared by the unit and
+    /// integration tests. Tests override only the fields they check:
+    /// `Review { rating: 5, ..Review::sample("1") }`.
+    #[doc(hidden)]
+    pub fn sample(review_id: &str) -> Self {
+        use chrono::TimeZone;
+
+        Review {
+            store: Store::PlayStore,
+            app_id: "com.example.app".to_string(),
+            country: "us".to_string(),
+            review_id: review_id.to_string(),
+            author: "Sam".to_string(),
+            author_uri: None,
+            date: Utc.timestamp_opt(1_746_900_000, 0).unwrap(),
+            rating: 2,
+            helpful_votes: 3,
+            total_votes: None,
+            version: Some("2.25.13.75".to_string()),
+            language: None,
+            title: None,
+            body: "crashes".to_string(),
+            developer_reply: None,
+        }
+    }
+}
@@ -1,10 +1,10 @@
 use reqwest::RequestBuilder;
 use serde::{Deserialize, Serialize};
 
-use crate::{
-    review_crawler::{get_client, get_default_pages, HasAppInfo, TBuildRequest},
-    APP_STORE_MAX_PAGES,
-};
+use crate::review_crawler::{get_client, get_default_pages, HasAppInfo, TBuildRequest};
+
+/// The customer reviews RSS feed serves at most 10 pages.
+pub const APP_STORE_MAX_PAGES: u32 = 10;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppStoreClient {
@@ -12,6 +12,24 @@ pub struct AppStoreClient {
     pub country: String,
     #[serde(default = "get_default_pages")]
     pub pages: u32,
+    // 마지막으로 요청할 페이지 (CLI에서 설정)
+    #[serde(skip, default = "get_app_store_max_pages")]
+    pub max_pages: u32,
+}
+
+impl AppStoreClient {
+    pub fn new(app_id: impl Into<String>, country: impl Into<String>) -> Self {
+        Self {
+            app_id: app_id.into(),
+            country: country.into(),
+            pages: get_default_pages(),
+            max_pages: APP_STORE_MAX_PAGES,
+        }
+    }
+}
+
+fn get_app_store_max_pages() -> u32 {
+    APP_STORE_MAX_PAGES
 }
 
 impl HasAppInfo for AppStoreClient {
@@ -32,10 +50,14 @@ impl TBuildRequest for AppStoreClient {
         ))
     }
     fn has_more_pages(&self) -> bool {
-        self.pages <= APP_STORE_MAX_PAGES
+        self.pages <= self.max_pages
+    }
+    fn next_page(&mut self, _response: &[u8]) {
+        self.pages += 1;
     }
-    fn increment_page(&mut self) {
+    fn skip_page(&mut self) -> bool {
         self.pages += 1;
+        true
     }
     fn get_current_page(&self) -> u32 {
         self.pages
@@ -48,11 +70,7 @@ mod tests {
 
     #[test]
     fn test_app_store_client_pagination() {
-        let mut client = AppStoreClient {
-            app_id: "test_app".to_string(),
-            country: "us".to_string(),
-            pages: 1,
-        };
+        let mut client = AppStoreClient::new("test_app", "us");
 
         // Test initial state
         assert_eq!(client.get_current_page(), 1);
@@ -62,7 +80,7 @@ mod tests {
         for expected_page in 1..=APP_STORE_MAX_PAGES {
             assert_eq!(client.get_current_page(), expected_page);
             assert!(client.has_more_pages());
-            client.increment_page();
+            client.next_page(&[]);
         }
 
         // After 10 pages, should not have more pages
@@ -70,12 +88,33 @@ mod tests {
         assert!(!client.has_more_pages());
     }
 
+    #[test]
+    fn test_app_store_client_skips_failed_page() {
+        let mut client = AppStoreClient::new("test_app", "us");
+
+        // 페이지 번호로 요청하므로 응답 없이도 다음 페이지로 넘어갈 수 있음
+        assert!(client.skip_page());
+        assert_eq!(client.get_current_page(), 2);
+    }
+
+    #[test]
+    fn test_app_store_client_custom_page_limit() {
+        let mut client = AppStoreClient {
+            max_pages: 2,
+            ..AppStoreClient::new("test_app", "us")
+        };
+
+        client.next_page(&[]);
+        assert!(client.has_more_pages());
+        client.next_page(&[]);
+        assert!(!client.has_more_pages());
+    }
+
     #[test]
     fn test_app_store_client_request_building() {
         let mut client = AppStoreClient {
-            app_id: "123456789".to_string(),
-            country: "kr".to_string(),
             pages: 5,
+            ..AppStoreClient::new("123456789", "kr")
         };
 
         let request = client.build_request();
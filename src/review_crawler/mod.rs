@@ -1,42 +1,84 @@
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
 
-use reqwest::{Client, Response};
+use bytes::Bytes;
+use futures::{stream, Stream};
+use reqwest::Client;
 
 use crate::errors::CrawlerError;
 
 pub mod app_store;
 pub mod play_store;
+pub mod rate_limit;
+pub mod retry;
 pub mod traits;
+pub use rate_limit::{RateLimit, RateLimiter};
+pub use retry::RetryPolicy;
 pub use traits::{HasAppInfo, TBuildRequest};
 
 pub struct Crawler<C: TBuildRequest> {
     client: C,
+    retry: RetryPolicy,
+    limiter: Option<Arc<RateLimiter>>,
 }
 
 impl<C: TBuildRequest> Crawler<C> {
     pub fn new(client: C) -> Self {
-        Self { client }
+        Self {
+            client,
+            retry: RetryPolicy::default(),
+            limiter: None,
+        }
     }
 
-    pub async fn run(&mut self) -> Result<Vec<Response>, CrawlerError> {
-        let mut responses = Vec::new();
-
-        // 페이지가 10이 될 때까지 계속 크롤링
-        while self.client.has_more_pages() {
-            tracing::debug!("Crawling page {}", self.client.get_current_page());
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
 
-            let response = self
-                .client
-                .build_request()
-                .send()
-                .await
-                .map_err(|e| CrawlerError::Request(e.to_string()))?;
+    /// Shares a store-wide rate limiter with this crawler.
+    pub fn with_rate_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        self.limiter = Some(limiter);
+        self
+    }
 
-            responses.push(response);
-            self.client.increment_page();
+    /// Fetches the next page, or returns `None` once the client has no more pages.
+    pub async fn next_page(&mut self) -> Result<Option<Bytes>, CrawlerError> {
+        if !self.client.has_more_pages() {
+            return Ok(None);
         }
+        tracing::debug!("Crawling page {}", self.client.get_current_page());
+
+        // 일시적인 실패(네트워크 오류, 429, 5xx)는 정책에 따라 재시도하고,
+        // 재시도를 포함한 모든 요청은 스토어 단위 rate limiter를 거침
+        let client = &mut self.client;
+        let body = self
+            .retry
+            .fetch(self.limiter.as_deref(), || client.build_request())
+            .await?;
+
+        // 다음 페이지 상태(페이지 번호 또는 continuation token)는 현재 응답에서 결정
+        self.client.next_page(&body);
+        Ok(Some(body))
+    }
 
-        Ok(responses)
+    /// Turns the crawler into a lazy stream of page bodies. A page is only
+    /// requested once the consumer polls for it, so dropping the stream stops
+    /// the crawl and at most one page is held in memory at a time.
+    ///
+    /// A page that cannot be fetched is yielded as an error. The stream goes
+    /// on with the next page if the client can skip it
+    /// ([`TBuildRequest::skip_page`]) and ends otherwise.
+    pub fn into_pages(self) -> impl Stream<Item = Result<Bytes, CrawlerError>> {
+        stream::unfold(Some(self), |crawler| async move {
+            let mut crawler = crawler?;
+            match crawler.next_page().await {
+                Ok(page) => page.map(|page| (Ok(page), Some(crawler))),
+                Err(e) => {
+                    let next = crawler.client.skip_page().then_some(crawler);
+                    Some((Err(e), next))
+                }
+            }
+        })
     }
 }
 
@@ -1,17 +1,123 @@
 use reqwest::RequestBuilder;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 
 use crate::{
+    response_processor::play_store::next_page_token,
     review_crawler::{get_client, get_default_pages, HasAppInfo, TBuildRequest},
-    GOOGLE_PLAY_MAX_PAGES,
 };
 
+/// Safety cap on continuation-token pages per app.
+pub const GOOGLE_PLAY_MAX_PAGES: u32 = 100;
+
+// Play Store 웹 클라이언트가 사용하는 batchexecute 리뷰 RPC
+const BATCHEXECUTE_URL: &str = "https://play.google.com/_/PlayStoreUi/data/batchexecute";
+const REVIEWS_RPC_ID: &str = "UsvDTd";
+const REVIEWS_PER_PAGE: u32 = 100;
+const SORT_BY_NEWEST: u32 = 2;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlayStoreClient {
     pub app_id: String,
     pub country: String,
+    /// Review language (`hl`), e.g. `"ko"`. Defaults to the main language of
+    /// `country`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
     #[serde(default = "get_default_pages")]
     pub pages: u32,
+    // 마지막으로 요청할 페이지 (CLI에서 설정)
+    #[serde(skip, default = "get_google_play_max_pages")]
+    pub max_pages: u32,
+    // 이전 응답에서 받은 continuation token
+    #[serde(skip)]
+    pub continuation: Option<String>,
+    // 마지막 응답에 continuation token이 없으면 더 이상 페이지가 없음
+    #[serde(skip)]
+    pub exhausted: bool,
+}
+
+impl PlayStoreClient {
+    pub fn new(app_id: impl Into<String>, country: impl Into<String>) -> Self {
+        Self {
+            app_id: app_id.into(),
+            country: country.into(),
+            language: None,
+            pages: get_default_pages(),
+            max_pages: GOOGLE_PLAY_MAX_PAGES,
+            continuation: None,
+            exhausted: false,
+        }
+    }
+
+    /// The configured language, or the default language of the country.
+    pub fn language(&self) -> &str {
+        self.language
+            .as_deref()
+            .unwrap_or_else(|| default_language(&self.country))
+    }
+
+    fn request_payload(&self) -> String {
+        let rpc_args = json!([
+            null,
+            null,
+            [
+                2,
+                SORT_BY_NEWEST,
+                [REVIEWS_PER_PAGE, null, self.continuation],
+                null,
+                []
+            ],
+            [self.app_id, 7]
+        ]);
+        json!([[[REVIEWS_RPC_ID, rpc_args.to_string(), null, "generic"]]]).to_string()
+    }
+}
+
+fn get_google_play_max_pages() -> u32 {
+    GOOGLE_PLAY_MAX_PAGES
+}
+
+/// Main language (ISO 639-1) of a country, English when it is not listed.
+pub fn default_language(country: &str) -> &'static str {
+    match country.to_ascii_lowercase().as_str() {
+        "kr" => "ko",
+        "jp" => "ja",
+        "cn" | "tw" | "hk" | "sg" => "zh",
+        "de" | "at" | "li" => "de",
+        "fr" | "mc" => "fr",
+        "es" | "mx" | "ar" | "cl" | "co" | "pe" | "ve" | "ec" | "uy" | "py" | "bo" | "cr"
+        | "pa" | "gt" | "hn" | "sv" | "ni" | "do" | "cu" => "es",
+        "br" | "pt" | "ao" | "mz" => "pt",
+        "it" | "sm" => "it",
+        "nl" => "nl",
+        "ru" | "by" | "kz" => "ru",
+        "ua" => "uk",
+        "pl" => "pl",
+        "cz" => "cs",
+        "sk" => "sk",
+        "hu" => "hu",
+        "ro" | "md" => "ro",
+        "bg" => "bg",
+        "gr" | "cy" => "el",
+        "tr" => "tr",
+        "se" => "sv",
+        "no" => "no",
+        "dk" => "da",
+        "fi" => "fi",
+        "il" => "he",
+        "sa" | "ae" | "eg" | "qa" | "kw" | "bh" | "om" | "jo" | "lb" | "ma" | "dz" | "tn"
+        | "iq" => "ar",
+        "ir" => "fa",
+        "th" => "th",
+        "vn" => "vi",
+        "id" => "id",
+        "my" => "ms",
+        "hr" => "hr",
+        "rs" => "sr",
+        "si" => "sl",
+        _ => "en",
+    }
 }
 
 impl HasAppInfo for PlayStoreClient {
@@ -25,18 +131,27 @@ impl HasAppInfo for PlayStoreClient {
 
 impl TBuildRequest for PlayStoreClient {
     fn build_request(&mut self) -> RequestBuilder {
-        // Play Store API endpoint (placeholder - needs actual implementation)
         get_client()
-            .get(format!(
-                "https://play.google.com/store/getreviews?hl={}&gl={}&reviewType=0&reviewSortOrder=4&pageNum={}&id={}",
-                self.country, self.country, self.pages, self.app_id
-            ))
+            .post(BATCHEXECUTE_URL)
+            .query(&[
+                ("rpcids", REVIEWS_RPC_ID),
+                ("source-path", "/store/apps/details"),
+                ("hl", self.language()),
+                ("gl", self.country.as_str()),
+                ("authuser", ""),
+                ("soc-app", "121"),
+                ("soc-platform", "1"),
+                ("soc-device", "1"),
+            ])
+            .form(&[("f.req", self.request_payload())])
     }
     fn has_more_pages(&self) -> bool {
-        self.pages <= GOOGLE_PLAY_MAX_PAGES
+        !self.exhausted && self.pages <= self.max_pages
     }
-    fn increment_page(&mut self) {
+    fn next_page(&mut self, response: &[u8]) {
         self.pages += 1;
+        self.continuation = next_page_token(response);
+        self.exhausted = self.continuation.is_none();
     }
     fn get_current_page(&self) -> u32 {
         self.pages
@@ -47,47 +162,119 @@ impl TBuildRequest for PlayStoreClient {
 mod tests {
     use super::*;
 
+    const PAGE: &str = include_str!("../../tests/fixtures/play_store/reviews_page.txt");
+    const LAST_PAGE: &str = include_str!("../../tests/fixtures/play_store/reviews_last_page.txt");
+
     #[test]
     fn test_play_store_client_pagination() {
-        let mut client = PlayStoreClient {
-            app_id: "test_app".to_string(),
-            country: "us".to_string(),
-            pages: 1,
-        };
+        let mut client = PlayStoreClient::new("test_app", "us");
 
         // Test initial state
         assert_eq!(client.get_current_page(), 1);
         assert!(client.has_more_pages());
 
-        // Test pagination through all pages (1 to 100)
-        for expected_page in 1..=GOOGLE_PLAY_MAX_PAGES {
-            assert_eq!(client.get_current_page(), expected_page);
+        // A page with a continuation token keeps the crawl going
+        client.next_page(PAGE.as_bytes());
+        assert_eq!(client.get_current_page(), 2);
+        assert!(client.continuation.is_some());
+        assert!(client.has_more_pages());
+
+        // A page without a token ends it
+        client.next_page(LAST_PAGE.as_bytes());
+        assert_eq!(client.get_current_page(), 3);
+        assert!(client.continuation.is_none());
+        assert!(!client.has_more_pages());
+    }
+
+    #[test]
+    fn test_play_store_client_cannot_skip_failed_page() {
+        let mut client = PlayStoreClient::new("test_app", "us");
+        client.next_page(PAGE.as_bytes());
+
+        // 다음 토큰은 실패한 페이지의 응답에 있으므로 건너뛸 수 없음
+        assert!(!client.skip_page());
+        assert_eq!(client.get_current_page(), 2);
+    }
+
+    #[test]
+    fn test_play_store_client_page_limit() {
+        let mut client = PlayStoreClient::new("test_app", "us");
+
+        for _ in 1..=GOOGLE_PLAY_MAX_PAGES {
             assert!(client.has_more_pages());
-            client.increment_page();
+            client.next_page(PAGE.as_bytes());
         }
 
-        // After 100 pages (1-100), should not have more pages
+        // Even with a token, stop after GOOGLE_PLAY_MAX_PAGES pages
         assert_eq!(client.get_current_page(), GOOGLE_PLAY_MAX_PAGES + 1);
+        assert!(client.continuation.is_some());
         assert!(!client.has_more_pages());
     }
 
     #[test]
     fn test_play_store_client_request_building() {
-        let mut client = PlayStoreClient {
-            app_id: "com.example.app".to_string(),
-            country: "kr".to_string(),
-            pages: 5,
-        };
+        let mut client = PlayStoreClient::new("com.example.app", "kr");
 
-        let request = client.build_request();
-        let url = request.build().unwrap().url().to_string();
+        let request = client.build_request().build().unwrap();
+        let url = request.url().to_string();
 
         // Check that the URL contains the expected components
-        assert!(url.contains("play.google.com"));
-        assert!(url.contains("kr"));
-        assert!(url.contains("com.example.app"));
-        assert!(url.contains("pageNum=5"));
-        assert!(url.contains("reviewType=0"));
-        assert!(url.contains("reviewSortOrder=4"));
+        assert_eq!(request.method(), reqwest::Method::POST);
+        assert!(url.starts_with(BATCHEXECUTE_URL));
+        assert!(url.contains("rpcids=UsvDTd"));
+        // hl는 언어 코드, gl은 국가 코드
+        assert!(url.contains("hl=ko&gl=kr"));
+
+        let body = String::from_utf8(request.body().unwrap().as_bytes().unwrap().to_vec()).unwrap();
+        assert!(body.starts_with("f.req="));
+        assert!(body.contains("com.example.app"));
+    }
+
+    #[test]
+    fn test_play_store_client_language() {
+        let mut client = PlayStoreClient::new("com.example.app", "jp");
+        assert!(client
+            .build_request()
+            .build()
+            .unwrap()
+            .url()
+            .as_str()
+            .contains("hl=ja&gl=jp"));
+
+        // 설정한 언어가 국가 기본 언어보다 우선
+        let mut client: PlayStoreClient = serde_json::from_str(
+            r#"{ "app_id": "com.example.app", "country": "ca", "language": "fr" }"#,
+        )
+        .unwrap();
+        assert_eq!(client.language(), "fr");
+        assert!(client
+            .build_request()
+            .build()
+            .unwrap()
+            .url()
+            .as_str()
+            .contains("hl=fr&gl=ca"));
+
+        assert_eq!(default_language("US"), "en");
+    }
+
+    #[test]
+    fn test_play_store_client_request_carries_continuation() {
+        let mut client = PlayStoreClient::new("com.example.app", "us");
+
+        let first = client.request_payload();
+        assert!(first.contains("[100,null,null]"));
+
+        client.next_page(PAGE.as_bytes());
+        let token = client.continuation.clone().unwrap();
+        let second = client.request_payload();
+        assert!(second.contains(&token));
+
+        // The RPC arguments are a JSON string nested inside the envelope
+        let envelope: serde_json::Value = serde_json::from_str(&second).unwrap();
+        let args: serde_json::Value =
+            serde_json::from_str(envelope[0][0][1].as_str().unwrap()).unwrap();
+        assert_eq!(args[2][2][2], token.as_str());
+        assert_eq!(args[3][0], "com.example.app");
     }
 }
@@ -0,0 +1,186 @@
+use std::time::Duration;
+
+use tokio::{sync::Mutex, time::Instant};
+
+use crate::errors::CrawlerError;
+
+/// Request budget for one store host.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimit {
+    /// Sustained rate the bucket refills at.
+    pub requests_per_second: f64,
+    /// How many requests may be sent back to back after an idle period.
+    pub burst: u32,
+}
+
+impl RateLimit {
+    /// The rate must be positive and finite and the burst at least 1.
+    pub fn validate(&self) -> Result<(), CrawlerError> {
+        // 너무 작은 값은 대기 시간이 Duration 범위를 넘어서므로 함께 거절
+        if !self.requests_per_second.is_finite()
+            || Duration::try_from_secs_f64(1.0 / self.requests_per_second).is_err()
+        {
+            return Err(CrawlerError::config(
+                Some("requests_per_second"),
+                format!(
+                    "must be a positive number, got {}",
+                    self.requests_per_second
+                ),
+            ));
+        }
+        if self.burst == 0 {
+            return Err(CrawlerError::config(Some("burst"), "must be at least 1"));
+        }
+        Ok(())
+    }
+}
+
+/// Token bucket shared (through an `Arc`) by every crawler talking to the same host.
+#[derive(Debug)]
+pub struct RateLimiter {
+    limit: RateLimit,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    /// Fails with [`CrawlerError::Config`] for a limit [`RateLimit::validate`] rejects.
+    pub fn new(limit: RateLimit) -> Result<Self, CrawlerError> {
+        limit.validate()?;
+        Ok(Self {
+            limit,
+            bucket: Mutex::new(Bucket {
+                tokens: limit.burst as f64,
+                refilled_at: Instant::now(),
+            }),
+        })
+    }
+
+    /// Waits until a request may be sent and takes one token.
+    pub async fn acquire(&self) {
+        // 락을 잡은 채로 기다리므로 대기 중인 요청들은 순서대로 처리됨
+        let mut bucket = self.bucket.lock().await;
+        self.refill(&mut bucket);
+
+        if bucket.tokens < 1.0 {
+            let wait = (1.0 - bucket.tokens) / self.limit.requests_per_second;
+            tracing::trace!("Rate limit reached, waiting {:.3}s", wait);
+            tokio::time::sleep(Duration::from_secs_f64(wait)).await;
+            self.refill(&mut bucket);
+        }
+
+        bucket.tokens = (bucket.tokens - 1.0).max(0.0);
+    }
+
+    fn refill(&self, bucket: &mut Bucket) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens =
+            (bucket.tokens + elapsed * self.limit.requests_per_second).min(self.limit.burst as f64);
+        bucket.refilled_at = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_burst_then_steady_rate() {
+        let limiter = RateLimiter::new(RateLimit {
+            requests_per_second: 2.0,
+            burst: 3,
+        })
+        .unwrap();
+        let started = Instant::now();
+
+        // burst 만큼은 바로 통과
+        for _ in 0..3 {
+            limiter.acquire().await;
+        }
+        assert_eq!(started.elapsed(), Duration::ZERO);
+
+        // 이후에는 초당 2개
+        limiter.acquire().await;
+        assert_eq!(started.elapsed(), Duration::from_millis(500));
+        limiter.acquire().await;
+        assert_eq!(started.elapsed(), Duration::from_millis(1000));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_tokens_refill_while_idle() {
+        let limiter = RateLimiter::new(RateLimit {
+            requests_per_second: 1.0,
+            burst: 2,
+        })
+        .unwrap();
+        limiter.acquire().await;
+        limiter.acquire().await;
+
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        let resumed = Instant::now();
+
+        // 쉬는 동안 burst 크기까지만 채워짐
+        limiter.acquire().await;
+        limiter.acquire().await;
+        assert_eq!(resumed.elapsed(), Duration::ZERO);
+        limiter.acquire().await;
+        assert_eq!(resumed.elapsed(), Duration::from_secs(1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_limiter_is_shared_between_tasks() {
+        let limiter = Arc::new(
+            RateLimiter::new(RateLimit {
+                requests_per_second: 4.0,
+                burst: 1,
+            })
+            .unwrap(),
+        );
+        let started = Instant::now();
+
+        let tasks: Vec<_> = (0..4)
+            .map(|_| {
+                let limiter = limiter.clone();
+                tokio::spawn(async move {
+                    limiter.acquire().await;
+                    limiter.acquire().await;
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        // 8개의 요청: 첫 요청은 바로, 나머지 7개는 0.25초 간격
+        assert_eq!(started.elapsed(), Duration::from_millis(1750));
+    }
+
+    #[test]
+    fn test_rejects_invalid_limits() {
+        let limit = |requests_per_second, burst| RateLimit {
+            requests_per_second,
+            burst,
+        };
+        for invalid in [0.0, -1.0, f64::MIN_POSITIVE, f64::NAN, f64::INFINITY] {
+            match RateLimiter::new(limit(invalid, 1)) {
+                Err(CrawlerError::Config { field, .. }) => {
+                    assert_eq!(field.as_deref(), Some("requests_per_second"))
+                }
+                other => panic!("Expected Config error for {invalid}, got {other:?}"),
+            }
+        }
+        match RateLimiter::new(limit(1.0, 0)) {
+            Err(CrawlerError::Config { field, .. }) => assert_eq!(field.as_deref(), Some("burst")),
+            other => panic!("Expected Config error, got {other:?}"),
+        }
+        assert!(RateLimiter::new(limit(0.01, 1)).is_ok());
+    }
+}
@@ -0,0 +1,336 @@
+use std::time::Duration;
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use reqwest::{header::RETRY_AFTER, RequestBuilder, Response, StatusCode};
+
+use crate::{errors::CrawlerError, review_crawler::rate_limit::RateLimiter};
+
+/// How a crawler retries transient failures (network errors, 408, 429 and 5xx).
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total attempts per page, including the first one.
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled on every further attempt.
+    pub base_delay: Duration,
+    /// Upper bound for any single delay, including one asked for by `Retry-After`.
+    pub max_delay: Duration,
+    /// Randomise each backoff delay between half and the full value.
+    pub jitter: bool,
+    /// Prefer the server's `Retry-After` header over the computed backoff.
+    pub respect_retry_after: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+            respect_retry_after: true,
+        }
+    }
+}
+
+enum Attempt {
+    Done(Bytes),
+    Retry(CrawlerError, Option<Duration>),
+    Fail(CrawlerError),
+}
+
+impl RetryPolicy {
+    /// Sends the request built by `build` and returns the body of the first
+    /// 2xx response. `build` is called again for every attempt, and every
+    /// attempt waits for the rate limiter first.
+    pub async fn fetch<F>(
+        &self,
+        limiter: Option<&RateLimiter>,
+        mut build: F,
+    ) -> Result<Bytes, CrawlerError>
+    where
+        F: FnMut() -> RequestBuilder,
+    {
+        let max_attempts = self.max_attempts.max(1);
+        let mut attempt = 1;
+
+        loop {
+            if let Some(limiter) = limiter {
+                limiter.acquire().await;
+            }
+            let (error, retry_after) = match Self::attempt(build()).await {
+                Attempt::Done(body) => return Ok(body),
+                Attempt::Fail(error) => return Err(error),
+                Attempt::Retry(error, retry_after) => (error, retry_after),
+            };
+
+            if attempt >= max_attempts {
+                tracing::warn!("Giving up after {} attempts: {}", attempt, error);
+                return Err(error);
+            }
+
+            let delay = match retry_after {
+                Some(retry_after) if self.respect_retry_after => retry_after.min(self.max_delay),
+                _ => self.backoff(attempt),
+            };
+            tracing::debug!(
+                "Attempt {}/{} failed ({}), retrying in {:?}",
+                attempt,
+                max_attempts,
+                error,
+                delay
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Exponential backoff for the given (1-based) failed attempt.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(31);
+        let delay = self
+            .base_delay
+            .saturating_mul(1 << exponent)
+            .min(self.max_delay);
+
+        if self.jitter && !delay.is_zero() {
+            let millis = delay.as_millis() as u64;
+            Duration::from_millis(rand::random_range(millis / 2..=millis))
+        } else {
+            delay
+        }
+    }
+
+    async fn attempt(request: RequestBuilder) -> Attempt {
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => return Attempt::failed(CrawlerError::from_request(e), None),
+        };
+
+        if let Err(source) = response.error_for_status_ref() {
+            let retry_after = parse_retry_after(&response);
+            let status = response.status();
+            let error = if status == StatusCode::TOO_MANY_REQUESTS {
+                CrawlerError::RateLimited {
+                    retry_after,
+                    source,
+                }
+            } else {
+                CrawlerError::Http {
+                    status,
+                    url: response.url().to_string(),
+                    source,
+                }
+            };
+            return Attempt::failed(error, retry_after);
+        }
+
+        match response.bytes().await {
+            Ok(body) => Attempt::Done(body),
+            Err(e) => Attempt::failed(CrawlerError::from_request(e), None),
+        }
+    }
+}
+
+impl Attempt {
+    // 요청을 만들 수 없거나 4xx처럼 다시 보내도 같은 결과인 오류는 바로 실패
+    fn failed(error: CrawlerError, retry_after: Option<Duration>) -> Self {
+        if error.is_transient() {
+            Attempt::Retry(error, retry_after)
+        } else {
+            Attempt::Fail(error)
+        }
+    }
+}
+
+// Retry-After는 초 단위 숫자 또는 HTTP-date 형식
+fn parse_retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    (date.with_timezone(&Utc) - Utc::now()).to_std().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::*;
+    use crate::review_crawler::get_client;
+
+    fn fast_policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(20),
+            jitter: true,
+            respect_retry_after: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_server_errors_until_success() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/reviews"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/reviews"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let url = format!("{}/reviews", server.uri());
+        let body = fast_policy(3)
+            .fetch(None, || get_client().get(&url))
+            .await
+            .unwrap();
+
+        assert_eq!(body.as_ref(), b"ok");
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let url = format!("{}/reviews", server.uri());
+        let result = fast_policy(3).fetch(None, || get_client().get(&url)).await;
+
+        match result {
+            Err(CrawlerError::Http { status, url, .. }) => {
+                assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+                assert!(url.ends_with("/reviews"));
+            }
+            other => panic!("Expected Http error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_client_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let url = format!("{}/reviews", server.uri());
+        let result = fast_policy(5).fetch(None, || get_client().get(&url)).await;
+
+        assert!(matches!(
+            result,
+            Err(CrawlerError::Http {
+                status: StatusCode::NOT_FOUND,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_honours_retry_after_on_429() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "1"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+            .mount(&server)
+            .await;
+
+        // Retry-After(1초)는 max_delay로 제한되지만 backoff(1ms)보다는 길어야 함
+        let policy = RetryPolicy {
+            max_delay: Duration::from_millis(200),
+            ..fast_policy(2)
+        };
+        let url = format!("{}/reviews", server.uri());
+        let started = std::time::Instant::now();
+        let body = policy.fetch(None, || get_client().get(&url)).await.unwrap();
+
+        assert_eq!(body.as_ref(), b"ok");
+        assert!(started.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_error_keeps_retry_after() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "7"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let url = format!("{}/reviews", server.uri());
+        let result = fast_policy(1).fetch(None, || get_client().get(&url)).await;
+
+        match result {
+            Err(error @ CrawlerError::RateLimited { .. }) => {
+                assert!(error.is_transient());
+                assert_eq!(error.to_string(), "Rate limited (retry after 7s)");
+            }
+            other => panic!("Expected RateLimited error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_network_errors() {
+        // 아무도 listen하지 않는 포트로 연결하면 연결 오류가 발생
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/reviews", listener.local_addr().unwrap());
+        drop(listener);
+
+        let mut attempts = 0;
+        let result = fast_policy(3)
+            .fetch(None, || {
+                attempts += 1;
+                get_client().get(&url)
+            })
+            .await;
+
+        assert!(matches!(result, Err(CrawlerError::Network { .. })));
+        assert_eq!(attempts, 3);
+    }
+
+    #[test]
+    fn test_backoff_is_exponential_and_capped() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(1000),
+            jitter: false,
+            respect_retry_after: true,
+        };
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(4), Duration::from_millis(800));
+        assert_eq!(policy.backoff(5), Duration::from_millis(1000));
+        assert_eq!(policy.backoff(40), Duration::from_millis(1000));
+
+        let jittered = RetryPolicy {
+            jitter: true,
+            ..policy
+        };
+        for attempt in 1..6 {
+            let delay = jittered.backoff(attempt);
+            let full = policy.backoff(attempt);
+            assert!(delay >= full / 2 && delay <= full);
+        }
+    }
+}
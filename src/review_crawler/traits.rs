@@ -3,7 +3,15 @@ use reqwest::RequestBuilder;
 pub trait TBuildRequest {
     fn build_request(&mut self) -> RequestBuilder;
     fn has_more_pages(&self) -> bool;
-    fn increment_page(&mut self);
+    /// Moves to the next page using the body of the page that was just fetched,
+    /// so stores with opaque continuation tokens can carry them forward.
+    fn next_page(&mut self, response: &[u8]);
+    /// Moves past a page whose request failed. Returns `false` when the next
+    /// page cannot be reached without the failed page's body (continuation
+    /// tokens), which ends the crawl.
+    fn skip_page(&mut self) -> bool {
+        false
+    }
     fn get_current_page(&self) -> u32;
 }
 
@@ -0,0 +1,550 @@
+use std::{
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+use futures::{future::join_all, stream, StreamExt};
+use tokio::task;
+use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
+
+use crate::{
+    errors::CrawlerError,
+    report::{AppReport, AppStatus, ErrorReport, RunReport},
+    response_processor::{
+        app_store::AppStoreExtractor,
+        play_store::PlayStoreExtractor,
+        sink::{PreparedSink, SinkConfig},
+        state::state_path,
+        traits::{TExtractData, TStoreType},
+        PageError, ResponseProcessor, RunStats,
+    },
+    review_crawler::{
+        app_store::{AppStoreClient, APP_STORE_MAX_PAGES},
+        play_store::{PlayStoreClient, GOOGLE_PLAY_MAX_PAGES},
+        Crawler, HasAppInfo, RateLimit, RateLimiter, RetryPolicy, TBuildRequest,
+    },
+};
+
+// * Number of apps crawled at once per store
+pub const APP_STORE_CONCURRENCY: usize = 4;
+pub const GOOGLE_PLAY_CONCURRENCY: usize = 2;
+
+// * Default retry policies (overridable from the CLI)
+pub const APP_STORE_RETRY: RetryPolicy = RetryPolicy {
+    max_attempts: 4,
+    base_delay: Duration::from_millis(500),
+    max_delay: Duration::from_secs(30),
+    jitter: true,
+    respect_retry_after: true,
+};
+pub const GOOGLE_PLAY_RETRY: RetryPolicy = RetryPolicy {
+    max_attempts: 5,
+    base_delay: Duration::from_secs(1),
+    max_delay: Duration::from_secs(60),
+    jitter: true,
+    respect_retry_after: true,
+};
+
+// * Default rate limits (shared by every crawl task of the same store; overridable from the CLI)
+pub const APP_STORE_RATE_LIMIT: RateLimit = RateLimit {
+    requests_per_second: 2.0,
+    burst: 5,
+};
+pub const GOOGLE_PLAY_RATE_LIMIT: RateLimit = RateLimit {
+    requests_per_second: 1.0,
+    burst: 3,
+};
+
+/// Per-store settings of a [`crawl`] run.
+#[derive(Debug, Clone)]
+pub struct StoreOptions {
+    pub concurrency: usize,
+    /// Last page to request per app.
+    pub max_pages: u32,
+    pub retry: RetryPolicy,
+    /// Shared by every crawl task of the store.
+    pub rate_limit: RateLimit,
+}
+
+impl StoreOptions {
+    /// The App Store defaults above.
+    pub fn app_store() -> Self {
+        Self {
+            concurrency: APP_STORE_CONCURRENCY,
+            max_pages: APP_STORE_MAX_PAGES,
+            retry: APP_STORE_RETRY,
+            rate_limit: APP_STORE_RATE_LIMIT,
+        }
+    }
+
+    /// The Play Store defaults above.
+    pub fn play_store() -> Self {
+        Self {
+            concurrency: GOOGLE_PLAY_CONCURRENCY,
+            max_pages: GOOGLE_PLAY_MAX_PAGES,
+            retry: GOOGLE_PLAY_RETRY,
+            rate_limit: GOOGLE_PLAY_RATE_LIMIT,
+        }
+    }
+}
+
+/// Settings of a [`crawl`] run over both stores.
+#[derive(Debug, Clone)]
+pub struct RunSettings {
+    pub output_dir: PathBuf,
+    /// Outputs every app is written to.
+    pub sinks: Vec<SinkConfig>,
+    /// Skip pages that fail instead of failing the whole app
+    /// (see [`ResponseProcessor::with_partial_success`]).
+    pub partial_success: bool,
+    pub app_store: StoreOptions,
+    pub play_store: StoreOptions,
+}
+
+/// What a [`crawl`] run did.
+#[derive(Debug)]
+pub struct RunOutcome {
+    pub report: RunReport,
+    /// Some app failed, or a store task panicked.
+    pub any_failed: bool,
+}
+
+/// Per-store crawl settings.
+pub struct StoreSettings {
+    pub concurrency: usize,
+    pub retry: RetryPolicy,
+    /// Shared by every crawl task of the store.
+    pub limiter: Arc<RateLimiter>,
+    pub output_dir: PathBuf,
+    /// Outputs every app is written to.
+    pub sinks: Vec<PreparedSink>,
+    /// Skip pages that fail instead of failing the whole app
+    /// (see [`ResponseProcessor::with_partial_success`]).
+    pub partial_success: bool,
+}
+
+/// Result of crawling one app.
+#[derive(Debug)]
+pub enum AppOutcome {
+    Succeeded,
+    /// Finished, but these pages were skipped.
+    Degraded(Vec<PageError>),
+    Failed(CrawlerError),
+    Cancelled,
+}
+
+/// Per-store totals of a crawl run.
+#[derive(Debug)]
+pub struct StoreSummary {
+    pub store_name: String,
+    pub succeeded: usize,
+    /// `(app label, skipped pages)` for every degraded app.
+    pub degraded: Vec<(String, Vec<PageError>)>,
+    /// `(app label, error)` for every failed app.
+    pub failed: Vec<(String, CrawlerError)>,
+    pub cancelled: usize,
+    /// One report per app, in config order.
+    pub apps: Vec<AppReport>,
+}
+
+impl StoreSummary {
+    pub fn log(&self) {
+        tracing::info!(
+            "{}: {} succeeded, {} degraded, {} failed, {} cancelled",
+            self.store_name,
+            self.succeeded,
+            self.degraded.len(),
+            self.failed.len(),
+            self.cancelled
+        );
+        for (app, pages) in &self.degraded {
+            for failed in pages {
+                tracing::warn!(
+                    "{} app {} skipped page {} ({}): {}",
+                    self.store_name,
+                    app,
+                    failed.page,
+                    failed.error.kind(),
+                    failed.error
+                );
+            }
+        }
+        for (app, error) in &self.failed {
+            tracing::error!(
+                "{} app {} failed ({}): {}",
+                self.store_name,
+                app,
+                error.kind(),
+                error
+            );
+        }
+    }
+}
+
+/// Crawls the given apps (`None` skips a store) the way the binary does: the
+/// sinks are prepared once, both stores run at the same time, and the run
+/// report is written to `output_dir`. Once `shutdown` is cancelled no new app
+/// is started. Fails before crawling if a rate limit is invalid or a sink
+/// cannot be prepared.
+pub async fn crawl(
+    settings: &RunSettings,
+    app_store_apps: Option<Vec<AppStoreClient>>,
+    play_store_apps: Option<Vec<PlayStoreClient>>,
+    shutdown: CancellationToken,
+) -> Result<RunOutcome, CrawlerError> {
+    tracing::info!("Starting app review crawler...");
+    let started_at = Utc::now();
+    let app_store_limiter = rate_limiter("App Store", settings.app_store.rate_limit)?;
+    let play_store_limiter = rate_limiter("Play Store", settings.play_store.rate_limit)?;
+    let sinks = prepare_sinks(&settings.sinks).await?;
+    let store_settings = |options: &StoreOptions, limiter| StoreSettings {
+        concurrency: options.concurrency,
+        retry: options.retry.clone(),
+        limiter,
+        output_dir: settings.output_dir.clone(),
+        sinks: sinks.clone(),
+        partial_success: settings.partial_success,
+    };
+    let mut handles = Vec::new();
+
+    if let Some(mut apps) = app_store_apps {
+        for app in &mut apps {
+            app.max_pages = settings.app_store.max_pages;
+        }
+        let store_settings = store_settings(&settings.app_store, app_store_limiter);
+        handles.push(task::spawn(
+            run_store_crawler("App Store", apps, store_settings, shutdown.clone(), |app| {
+                AppStoreExtractor::new(&app.app_id, &app.country)
+            })
+            .instrument(tracing::info_span!("App Store crawler")),
+        ));
+    }
+
+    if let Some(mut apps) = play_store_apps {
+        for app in &mut apps {
+            app.max_pages = settings.play_store.max_pages;
+        }
+        let store_settings = store_settings(&settings.play_store, play_store_limiter);
+        handles.push(task::spawn(
+            run_store_crawler(
+                "Play Store",
+                apps,
+                store_settings,
+                shutdown.clone(),
+                |app| PlayStoreExtractor::new(&app.app_id, &app.country, app.language()),
+            )
+            .instrument(tracing::info_span!("Play Store crawler")),
+        ));
+    }
+
+    let mut any_failed = false;
+    let mut apps = Vec::new();
+    for result in join_all(handles).await {
+        match result {
+            Ok(summary) => {
+                summary.log();
+                any_failed |= !summary.failed.is_empty();
+                apps.extend(summary.apps);
+            }
+            Err(e) => {
+                tracing::error!("Crawler task panicked: {}", e);
+                any_failed = true;
+            }
+        }
+    }
+
+    let report = RunReport {
+        started_at,
+        finished_at: Utc::now(),
+        apps,
+    };
+    match report.write(&settings.output_dir) {
+        Ok(path) => tracing::info!("Run report written to {}", path.display()),
+        Err(e) => tracing::error!("Failed to write run report: {}", e),
+    }
+
+    tracing::info!("Crawler finished");
+    Ok(RunOutcome { report, any_failed })
+}
+
+/// Connects the sinks every app shares (PostgreSQL) once for the run.
+pub async fn prepare_sinks(sinks: &[SinkConfig]) -> Result<Vec<PreparedSink>, CrawlerError> {
+    let mut prepared = Vec::with_capacity(sinks.len());
+    for sink in sinks {
+        let sink = sink.prepare().await.inspect_err(|e| {
+            tracing::error!("Failed to prepare sinks: {}", e);
+        })?;
+        prepared.push(sink);
+    }
+    Ok(prepared)
+}
+
+fn rate_limiter(store_name: &str, limit: RateLimit) -> Result<Arc<RateLimiter>, CrawlerError> {
+    RateLimiter::new(limit).map(Arc::new).inspect_err(|e| {
+        tracing::error!("Invalid {} rate limit: {}", store_name, e);
+    })
+}
+
+/// Crawls every app of one store, `settings.concurrency` at a time. Once
+/// `shutdown` is cancelled no new app is started and apps in flight are abandoned.
+/// `make_extractor` builds the extractor for each app.
+pub async fn run_store_crawler<C, D, F>(
+    store_name: &str,
+    apps: Vec<C>,
+    settings: StoreSettings,
+    shutdown: CancellationToken,
+    make_extractor: F,
+) -> StoreSummary
+where
+    C: TBuildRequest + HasAppInfo + Clone + Send + 'static,
+    D: TExtractData + TStoreType + Send + 'static,
+    F: Fn(&C) -> D,
+{
+    tracing::info!("Starting {} crawler task", store_name);
+    tracing::info!(
+        "Found {} {} apps to crawl (concurrency: {})",
+        apps.len(),
+        store_name,
+        settings.concurrency
+    );
+
+    let total = apps.len();
+    let settings = &settings;
+    let shutdown = &shutdown;
+    let make_extractor = &make_extractor;
+
+    // 같은 출력 파일을 쓰는 앱들은 한 작업 안에서 설정 순서대로 처리하고,
+    // 서로 다른 작업들은 concurrency 개수만큼 동시에 실행
+    let outcomes: Vec<Vec<(usize, String, AppOutcome, AppReport)>> =
+        stream::iter(group_by_app_id(apps))
+            .map(|job| async move {
+                let mut outcomes = Vec::new();
+                for (i, app) in job {
+                    let label = format!("{} ({})", app.app_id(), app.country());
+                    let extractor = make_extractor(&app);
+                    let mut report = AppReport {
+                        store: extractor.store(),
+                        app_id: app.app_id().to_string(),
+                        country: app.country().to_string(),
+                        status: AppStatus::Cancelled,
+                        stats: RunStats::default(),
+                        errors: Vec::new(),
+                        duration: Duration::ZERO,
+                    };
+                    // 종료 요청 이후에는 새 앱을 시작하지 않음
+                    if shutdown.is_cancelled() {
+                        outcomes.push((i, label, AppOutcome::Cancelled, report));
+                        continue;
+                    }
+                    tracing::info!("Crawling {} app {}/{}: {}", store_name, i + 1, total, label);
+                    let started = Instant::now();
+                    let (outcome, stats) = crawl_app(app, extractor, settings, shutdown).await;
+                    report.duration = started.elapsed();
+                    report.stats = stats;
+                    report.status = match &outcome {
+                        AppOutcome::Succeeded => AppStatus::Succeeded,
+                        AppOutcome::Degraded(pages) => {
+                            report.errors.extend(pages.iter().map(ErrorReport::from));
+                            AppStatus::Degraded
+                        }
+                        AppOutcome::Failed(e) => {
+                            report.errors.push(ErrorReport::from(e));
+                            AppStatus::Failed
+                        }
+                        AppOutcome::Cancelled => AppStatus::Cancelled,
+                    };
+                    outcomes.push((i, label, outcome, report));
+                }
+                outcomes
+            })
+            .buffer_unordered(settings.concurrency.max(1))
+            .collect()
+            .await;
+
+    let mut outcomes: Vec<_> = outcomes.into_iter().flatten().collect();
+    outcomes.sort_by_key(|(i, ..)| *i);
+
+    let mut summary = StoreSummary {
+        store_name: store_name.to_string(),
+        succeeded: 0,
+        degraded: Vec::new(),
+        failed: Vec::new(),
+        cancelled: 0,
+        apps: Vec::with_capacity(outcomes.len()),
+    };
+    for (_, label, outcome, report) in outcomes {
+        match outcome {
+            AppOutcome::Succeeded => summary.succeeded += 1,
+            AppOutcome::Degraded(pages) => summary.degraded.push((label, pages)),
+            AppOutcome::Failed(e) => summary.failed.push((label, e)),
+            AppOutcome::Cancelled => summary.cancelled += 1,
+        }
+        summary.apps.push(report);
+    }
+    summary
+}
+
+async fn crawl_app<C, D>(
+    app: C,
+    extractor: D,
+    settings: &StoreSettings,
+    shutdown: &CancellationToken,
+) -> (AppOutcome, RunStats)
+where
+    C: TBuildRequest + HasAppInfo,
+    D: TExtractData + TStoreType,
+{
+    let app_id = app.app_id().to_string();
+    let store = extractor.store();
+    let country = app.country().to_string();
+    let state = state_path(&settings.output_dir, store, &app_id, &country);
+
+    let mut processor = ResponseProcessor::new(extractor).with_state_file(state);
+    if settings.partial_success {
+        processor = processor.with_partial_success();
+    }
+    for sink in &settings.sinks {
+        match sink
+            .open(&settings.output_dir, store, &app_id, &country)
+            .await
+        {
+            Ok(sink) => processor = processor.with_sink(sink),
+            Err(e) => {
+                tracing::error!("Failed to open {:?} sink for app {}: {}", sink, app_id, e);
+                return (AppOutcome::Failed(e), RunStats::default());
+            }
+        }
+    }
+    let crawler = Crawler::new(app)
+        .with_retry_policy(settings.retry.clone())
+        .with_rate_limiter(settings.limiter.clone());
+
+    // 중단된 경우에는 통계를 받을 수 없으므로 빈 통계로 기록
+    tokio::select! {
+        (stats, result) = processor.run_with_stats(crawler.into_pages()) => match result {
+            Ok(failed_pages) if failed_pages.is_empty() => {
+                tracing::info!(
+                    "Successfully processed and saved reviews for app: {}",
+                    app_id
+                );
+                (AppOutcome::Succeeded, stats)
+            }
+            Ok(failed_pages) => {
+                tracing::warn!(
+                    "Saved reviews for app {} with {} failed pages",
+                    app_id,
+                    failed_pages.len()
+                );
+                (AppOutcome::Degraded(failed_pages), stats)
+            }
+            Err(e) => {
+                tracing::error!("Failed to crawl app {}: {}", app_id, e);
+                (AppOutcome::Failed(e), stats)
+            }
+        },
+        _ = shutdown.cancelled() => {
+            tracing::warn!("Crawl of app {} interrupted by shutdown", app_id);
+            (AppOutcome::Cancelled, RunStats::default())
+        }
+    }
+}
+
+/// Groups apps that share an app ID (and therefore an output file) into one
+/// job, keeping config order both across and within jobs.
+fn group_by_app_id<C: HasAppInfo>(apps: Vec<C>) -> Vec<Vec<(usize, C)>> {
+    let mut jobs: Vec<Vec<(usize, C)>> = Vec::new();
+    for (i, app) in apps.into_iter().enumerate() {
+        match jobs
+            .iter_mut()
+            .find(|job| job[0].1.app_id() == app.app_id())
+        {
+            Some(job) => job.push((i, app)),
+            None => jobs.push(vec![(i, app)]),
+        }
+    }
+    jobs
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::review_crawler::app_store::AppStoreClient;
+
+    fn app(app_id: &str, country: &str) -> AppStoreClient {
+        AppStoreClient::new(app_id, country)
+    }
+
+    fn settings(output_dir: &std::path::Path) -> RunSettings {
+        RunSettings {
+            output_dir: output_dir.to_path_buf(),
+            sinks: vec![SinkConfig::Csv],
+            partial_success: true,
+            app_store: StoreOptions::app_store(),
+            play_store: StoreOptions::play_store(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_crawl_without_apps_writes_an_empty_report() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let outcome = crawl(&settings(dir.path()), None, None, CancellationToken::new())
+            .await
+            .unwrap();
+
+        assert!(!outcome.any_failed);
+        assert!(outcome.report.apps.is_empty());
+        assert!(outcome.report.path(dir.path()).exists());
+    }
+
+    #[tokio::test]
+    async fn test_crawl_rejects_invalid_rate_limit_before_crawling() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut settings = settings(dir.path());
+        settings.play_store.rate_limit.burst = 0;
+
+        let result = crawl(
+            &settings,
+            Some(vec![app("1", "us")]),
+            None,
+            CancellationToken::new(),
+        )
+        .await;
+
+        assert!(matches!(result, Err(CrawlerError::Config { .. })));
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_group_by_app_id_keeps_config_order() {
+        let apps = vec![
+            app("1", "us"),
+            app("2", "us"),
+            app("1", "kr"),
+            app("3", "jp"),
+        ];
+
+        let jobs: Vec<Vec<(usize, String)>> = group_by_app_id(apps)
+            .into_iter()
+            .map(|job| {
+                job.into_iter()
+                    .map(|(i, app)| (i, format!("{}:{}", app.app_id, app.country)))
+                    .collect()
+            })
+            .collect();
+
+        assert_eq!(
+            jobs,
+            vec![
+                vec![(0, "1:us".to_string()), (2, "1:kr".to_string())],
+                vec![(1, "2:us".to_string())],
+                vec![(3, "3:jp".to_string())],
+            ]
+        );
+    }
+}
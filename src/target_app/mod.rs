@@ -1,13 +1,17 @@
-use std::{fs::File, io::BufReader};
+use std::path::Path;
 
 use serde::Deserialize;
 use tokio::sync::RwLock;
 
 use crate::{
     errors::CrawlerError,
+    response_processor::sink::{default_sinks, SinkConfig},
     review_crawler::{app_store::AppStoreClient, play_store::PlayStoreClient},
 };
 
+mod validate;
+pub use validate::{check_app, ConfigProblem, ConfigProblems};
+
 // JSON 구조에 맞는 중간 구조체들
 #[derive(Debug, Deserialize)]
 struct ClientsConfig {
@@ -15,46 +19,61 @@ struct ClientsConfig {
     app_store: Option<Vec<AppStoreClient>>,
     #[serde(default)]
     play_store: Option<Vec<PlayStoreClient>>,
+    #[serde(default)]
+    sinks: Option<Vec<SinkConfig>>,
 }
 
 #[derive(Debug)]
 pub struct Clients {
     pub app_store_apps: RwLock<Vec<AppStoreClient>>,
     pub play_store_apps: RwLock<Vec<PlayStoreClient>>,
+    /// Outputs selected in the config (CSV when none are given).
+    pub sinks: Vec<SinkConfig>,
 }
 
-pub fn load_target_apps(path: &str) -> Result<Clients, CrawlerError> {
-    tracing::debug!("Starting load_target_apps with path: {}", path);
+pub fn load_target_apps(path: impl AsRef<Path>) -> Result<Clients, CrawlerError> {
+    let path = path.as_ref();
+    tracing::debug!("Starting load_target_apps with path: {}", path.display());
 
-    // 파일 열기
-    tracing::debug!("Attempting to open file: {}", path);
-    let file = match File::open(path) {
-        Ok(file) => {
-            tracing::debug!("Successfully opened file");
-            file
+    // 파일 읽기
+    tracing::debug!("Attempting to read file: {}", path.display());
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => {
+            tracing::debug!("Successfully read file");
+            text
         }
         Err(e) => {
-            tracing::error!("Failed to open file: {}", e);
-            return Err(CrawlerError::ConfigLoad(e.to_string()));
+            tracing::error!("Failed to read file: {}", e);
+            return Err(CrawlerError::io(path, e));
         }
     };
 
-    let reader = BufReader::new(file);
-    tracing::debug!("Created BufReader");
+    parse_target_apps(&text)
+}
 
+/// Parses and validates the text of a target apps config file. Every problem
+/// found is reported at once, as [`ConfigProblems`] with line and column.
+pub fn parse_target_apps(text: &str) -> Result<Clients, CrawlerError> {
     // JSON 파싱
     tracing::debug!("Attempting to parse JSON");
-    let config: ClientsConfig = match serde_json::from_reader(reader) {
+    let config: ClientsConfig = match serde_json::from_str(text) {
         Ok(config) => {
             tracing::debug!("Successfully parsed JSON");
             config
         }
         Err(e) => {
             tracing::error!("Failed to parse JSON: {}", e);
-            return Err(CrawlerError::ConfigLoad(e.to_string()));
+            return Err(ConfigProblems(vec![ConfigProblem::from_json(&e)]).into());
         }
     };
 
+    // 형식은 맞지만 크롤링할 수 없는 값 검사
+    let problems = validate::check(text, &config);
+    if !problems.is_empty() {
+        tracing::error!("Found {} problems in the config", problems.len());
+        return Err(ConfigProblems(problems).into());
+    }
+
     tracing::debug!("Parsed config: {:?}", config);
 
     // App Store 앱들 처리
@@ -83,10 +102,14 @@ pub fn load_target_apps(path: &str) -> Result<Clients, CrawlerError> {
         }
     };
 
+    let sinks = config.sinks.unwrap_or_else(default_sinks);
+    tracing::debug!("Using sinks: {:?}", sinks);
+
     tracing::debug!("Successfully created Clients struct");
     Ok(Clients {
         app_store_apps,
         play_store_apps,
+        sinks,
     })
 }
 
@@ -95,16 +118,7 @@ mod tests {
     use super::*;
 
     fn load_target_apps_from_json(json_content: &str) -> Result<Clients, CrawlerError> {
-        let config: ClientsConfig = serde_json::from_str(json_content)
-            .map_err(|e| CrawlerError::ConfigLoad(e.to_string()))?;
-
-        let app_store_apps = RwLock::new(config.app_store.unwrap_or_default());
-        let play_store_apps = RwLock::new(config.play_store.unwrap_or_default());
-
-        Ok(Clients {
-            app_store_apps,
-            play_store_apps,
-        })
+        parse_target_apps(json_content)
     }
 
     #[tokio::test]
@@ -248,8 +262,22 @@ mod tests {
         assert_eq!(target_apps.play_store_apps.read().await.len(), 0);
     }
 
-    #[tokio::test]
-    async fn test_load_target_apps_with_unknown_keys() {
+    #[test]
+    fn test_load_target_apps_sinks() {
+        // sinks가 없으면 CSV만 사용
+        let target_apps = load_target_apps_from_json("{}").unwrap();
+        assert_eq!(target_apps.sinks, vec![SinkConfig::Csv]);
+
+        let json_content = r#"{ "sinks": [{ "type": "csv" }] }"#;
+        let target_apps = load_target_apps_from_json(json_content).unwrap();
+        assert_eq!(target_apps.sinks, vec![SinkConfig::Csv]);
+
+        let json_content = r#"{ "sinks": [{ "type": "carrier_pigeon" }] }"#;
+        assert!(load_target_apps_from_json(json_content).is_err());
+    }
+
+    #[test]
+    fn test_load_target_apps_with_unknown_keys() {
         let json_content = r#"
         {
             "app_store": [
@@ -269,17 +297,45 @@ mod tests {
 
         let result = load_target_apps_from_json(json_content);
 
-        assert!(result.is_ok());
-        let target_apps = result.unwrap();
+        // 알 수 없는 키는 무시하지 않고 위치와 함께 오류로 보고
+        match result.unwrap_err() {
+            error @ CrawlerError::Config { .. } => {
+                assert_eq!(
+                    error.to_string(),
+                    "Config error in `unknown_store`: line 9, column 13: \
+                     unknown key, expected one of `app_store`, `play_store`, `sinks`"
+                );
+                let CrawlerError::Config { field, source } = error else {
+                    unreachable!()
+                };
+                assert_eq!(field.as_deref(), Some("unknown_store"));
+                let problems = source.downcast_ref::<ConfigProblems>().unwrap();
+                assert_eq!(problems.0.len(), 1);
+                let problem = &problems.0[0];
+                assert_eq!(problem.field.as_deref(), Some("unknown_store"));
+                assert_eq!((problem.line, problem.column), (9, 13));
+            }
+            _ => panic!("Expected Config"),
+        }
+    }
 
-        // unknown_store는 무시되고 app_store만 처리되어야 함
-        assert_eq!(target_apps.app_store_apps.read().await.len(), 1);
-        assert_eq!(target_apps.play_store_apps.read().await.len(), 0);
+    #[test]
+    fn test_several_config_problems_name_no_single_field() {
+        let json_content = r#"{ "app_store": [{ "app_id": "abc", "country": "zz" }] }"#;
+
+        match load_target_apps_from_json(json_content).unwrap_err() {
+            CrawlerError::Config { field, source } => {
+                assert_eq!(field, None);
+                assert!(source.to_string().starts_with("2 problems:"));
+            }
+            _ => panic!("Expected Config"),
+        }
+    }
 
-        let app_store_apps = target_apps.app_store_apps.read().await;
-        let app = &app_store_apps[0];
-        assert_eq!(app.app_id, "123456789");
-        assert_eq!(app.country, "us");
+    #[test]
+    fn test_example_config_is_valid() {
+        let text = include_str!("../../target_apps.json.example");
+        assert!(parse_target_apps(text).is_ok());
     }
 
     #[test]
@@ -291,8 +347,8 @@ mod tests {
         // 잘못된 JSON은 에러를 반환해야 함
         assert!(result.is_err());
         match result.unwrap_err() {
-            CrawlerError::ConfigLoad(_) => (), // 예상된 에러 타입
-            _ => panic!("Expected ConfigLoad"),
+            CrawlerError::Config { .. } => (), // 예상된 에러 타입
+            _ => panic!("Expected Config"),
         }
     }
 
@@ -303,8 +359,8 @@ mod tests {
         // 존재하지 않는 파일은 에러를 반환해야 함
         assert!(result.is_err());
         match result.unwrap_err() {
-            CrawlerError::ConfigLoad(_) => (), // 예상된 에러 타입
-            _ => panic!("Expected ConfigLoad"),
+            CrawlerError::Io { path, .. } => assert_eq!(path, Path::new("nonexistent_file.json")), // 예상된 에러 타입
+            _ => panic!("Expected Io"),
         }
     }
 }
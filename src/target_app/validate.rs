@@ -0,0 +1,504 @@
+use std::{collections::HashMap, error::Error, fmt};
+
+use crate::{errors::CrawlerError, review::Store, review_crawler::HasAppInfo};
+
+use super::ClientsConfig;
+
+const KNOWN_KEYS: [&str; 3] = ["app_store", "play_store", "sinks"];
+
+/// Officially assigned ISO 3166-1 alpha-2 codes.
+const COUNTRY_CODES: [&str; 249] = [
+    "AD", "AE", "AF", "AG", "AI", "AL", "AM", "AO", "AQ", "AR", "AS", "AT", "AU", "AW", "AX", "AZ",
+    "BA", "BB", "BD", "BE", "BF", "BG", "BH", "BI", "BJ", "BL", "BM", "BN", "BO", "BQ", "BR", "BS",
+    "BT", "BV", "BW", "BY", "BZ", "CA", "CC", "CD", "CF", "CG", "CH", "CI", "CK", "CL", "CM", "CN",
+    "CO", "CR", "CU", "CV", "CW", "CX", "CY", "CZ", "DE", "DJ", "DK", "DM", "DO", "DZ", "EC", "EE",
+    "EG", "EH", "ER", "ES", "ET", "FI", "FJ", "FK", "FM", "FO", "FR", "GA", "GB", "GD", "GE", "GF",
+    "GG", "GH", "GI", "GL", "GM", "GN", "GP", "GQ", "GR", "GS", "GT", "GU", "GW", "GY", "HK", "HM",
+    "HN", "HR", "HT", "HU", "ID", "IE", "IL", "IM", "IN", "IO", "IQ", "IR", "IS", "IT", "JE", "JM",
+    "JO", "JP", "KE", "KG", "KH", "KI", "KM", "KN", "KP", "KR", "KW", "KY", "KZ", "LA", "LB", "LC",
+    "LI", "LK", "LR", "LS", "LT", "LU", "LV", "LY", "MA", "MC", "MD", "ME", "MF", "MG", "MH", "MK",
+    "ML", "MM", "MN", "MO", "MP", "MQ", "MR", "MS", "MT", "MU", "MV", "MW", "MX", "MY", "MZ", "NA",
+    "NC", "NE", "NF", "NG", "NI", "NL", "NO", "NP", "NR", "NU", "NZ", "OM", "PA", "PE", "PF", "PG",
+    "PH", "PK", "PL", "PM", "PN", "PR", "PS", "PT", "PW", "PY", "QA", "RE", "RO", "RS", "RU", "RW",
+    "SA", "SB", "SC", "SD", "SE", "SG", "SH", "SI", "SJ", "SK", "SL", "SM", "SN", "SO", "SR", "SS",
+    "ST", "SV", "SX", "SY", "SZ", "TC", "TD", "TF", "TG", "TH", "TJ", "TK", "TL", "TM", "TN", "TO",
+    "TR", "TT", "TV", "TW", "TZ", "UA", "UG", "UM", "US", "UY", "UZ", "VA", "VC", "VE", "VG", "VI",
+    "VN", "VU", "WF", "WS", "YE", "YT", "ZA", "ZM", "ZW",
+];
+
+/// One problem in the config file, with the position it was found at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigProblem {
+    /// Path of the offending value (`play_store[1].country`), when known.
+    pub field: Option<String>,
+    /// 1-based line and column.
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ConfigProblem {
+    /// A syntax or type error reported by serde_json.
+    pub(super) fn from_json(e: &serde_json::Error) -> Self {
+        let message = e.to_string();
+        // serde_json이 붙이는 위치 정보는 따로 보관
+        let suffix = format!(" at line {} column {}", e.line(), e.column());
+        let message = message.strip_suffix(&suffix).unwrap_or(&message);
+        Self {
+            field: None,
+            line: e.line(),
+            column: e.column(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ConfigProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(field) = &self.field {
+            write!(f, "`{field}` at ")?;
+        }
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+/// Every problem found in a config file, in file order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigProblems(pub Vec<ConfigProblem>);
+
+impl ConfigProblems {
+    /// 문제가 하나뿐이면 그 필드, 여러 개를 모아 보고하면 `None`
+    pub fn field(&self) -> Option<&str> {
+        match self.0.as_slice() {
+            [problem] => problem.field.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+impl From<ConfigProblems> for CrawlerError {
+    fn from(problems: ConfigProblems) -> Self {
+        let field = problems.field().map(str::to_string);
+        CrawlerError::config(field.as_deref(), problems)
+    }
+}
+
+impl fmt::Display for ConfigProblems {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.as_slice() {
+            // 필드 이름은 CrawlerError::Config가 이미 보여줌
+            [problem] => write!(
+                f,
+                "line {}, column {}: {}",
+                problem.line, problem.column, problem.message
+            ),
+            problems => {
+                write!(f, "{} problems:", problems.len())?;
+                for problem in problems {
+                    write!(f, "\n  {problem}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for ConfigProblems {}
+
+/// Checks what serde accepts but the crawler cannot use: unknown top-level
+/// keys, malformed app IDs and country codes, and apps listed twice.
+/// `text` must be the JSON `config` was parsed from.
+pub(super) fn check(text: &str, config: &ClientsConfig) -> Vec<ConfigProblem> {
+    let mut checker = Checker {
+        text,
+        positions: Positions::scan(text),
+        problems: Vec::new(),
+    };
+
+    for (key, offset) in checker.positions.top_level_keys.clone() {
+        if KNOWN_KEYS.contains(&key.as_str()) {
+            continue;
+        }
+        let message = match key.strip_suffix("_apps") {
+            Some(known) if KNOWN_KEYS.contains(&known) => {
+                format!("unknown key, did you mean `{known}`?")
+            }
+            _ => format!("unknown key, expected one of `{}`", KNOWN_KEYS.join("`, `")),
+        };
+        checker.report_at(key, offset, message);
+    }
+
+    if let Some(apps) = &config.app_store {
+        checker.check_apps("app_store", apps, check_app_store_id);
+    }
+    if let Some(apps) = &config.play_store {
+        checker.check_apps("play_store", apps, check_play_store_id);
+        for (i, app) in apps.iter().enumerate() {
+            let Some(language) = &app.language else {
+                continue;
+            };
+            if !is_language_code(language) {
+                let message = format!("{language:?} is not an ISO 639-1 language code");
+                checker.report(format!("play_store[{i}].language"), message);
+            }
+        }
+    }
+
+    let mut problems = checker.problems;
+    problems.sort_by_key(|problem| (problem.line, problem.column));
+    problems
+}
+
+struct Checker<'a> {
+    text: &'a str,
+    positions: Positions,
+    problems: Vec<ConfigProblem>,
+}
+
+impl Checker<'_> {
+    fn check_apps<C: HasAppInfo>(
+        &mut self,
+        store: &str,
+        apps: &[C],
+        check_id: fn(&str) -> Option<String>,
+    ) {
+        // (app_id, 소문자 국가 코드) -> 처음 나온 위치
+        let mut seen: HashMap<(&str, String), usize> = HashMap::new();
+        for (i, app) in apps.iter().enumerate() {
+            let entry = format!("{store}[{i}]");
+
+            if let Some(message) = check_id(app.app_id()) {
+                self.report(format!("{entry}.app_id"), message);
+            }
+            if !is_country_code(app.country()) {
+                let message = format!(
+                    "{:?} is not an ISO 3166-1 alpha-2 country code",
+                    app.country()
+                );
+                self.report(format!("{entry}.country"), message);
+            }
+
+            let key = (app.app_id(), app.country().to_ascii_lowercase());
+            match seen.get(&key) {
+                Some(first) => {
+                    let message = format!("duplicate of `{store}[{first}]`");
+                    self.report(entry, message);
+                }
+                None => {
+                    seen.insert(key, i);
+                }
+            }
+        }
+    }
+
+    /// Reports a problem at the value of `field`.
+    fn report(&mut self, field: String, message: String) {
+        let offset = self.positions.value(&field);
+        self.report_at(field, offset, message);
+    }
+
+    fn report_at(&mut self, field: String, offset: usize, message: String) {
+        let (line, column) = line_column(self.text, offset);
+        self.problems.push(ConfigProblem {
+            field: Some(field),
+            line,
+            column,
+            message,
+        });
+    }
+}
+
+/// Checks an app given outside a config file (such as on the command line)
+/// with the rules the config uses: the store's ID format and an ISO 3166-1
+/// alpha-2 country code.
+pub fn check_app(store: Store, app_id: &str, country: &str) -> Result<(), CrawlerError> {
+    let check_id = match store {
+        Store::AppStore => check_app_store_id,
+        Store::PlayStore => check_play_store_id,
+    };
+    if let Some(message) = check_id(app_id) {
+        return Err(CrawlerError::config(Some("app_id"), message));
+    }
+    if !is_country_code(country) {
+        let message = format!("{country:?} is not an ISO 3166-1 alpha-2 country code");
+        return Err(CrawlerError::config(Some("country"), message));
+    }
+    Ok(())
+}
+
+fn check_app_store_id(id: &str) -> Option<String> {
+    if !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some(match id.strip_prefix("id") {
+        Some(rest) if !rest.is_empty() && rest.bytes().all(|b| b.is_ascii_digit()) => {
+            format!("App Store ID must be numeric, drop the `id` prefix of {id:?}")
+        }
+        _ => format!("App Store ID must be numeric, got {id:?}"),
+    })
+}
+
+// Java 패키지 이름 규칙: 두 개 이상의 세그먼트, 각 세그먼트는 문자로 시작
+fn check_play_store_id(id: &str) -> Option<String> {
+    let segments: Vec<&str> = id.split('.').collect();
+    let valid = segments.len() >= 2
+        && segments.iter().all(|segment| {
+            segment.starts_with(|c: char| c.is_ascii_alphabetic())
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        });
+    (!valid).then(|| {
+        format!("Play Store ID must be a package name like \"com.example.app\", got {id:?}")
+    })
+}
+
+fn is_country_code(code: &str) -> bool {
+    code.len() == 2
+        && COUNTRY_CODES
+            .binary_search(&code.to_ascii_uppercase().as_str())
+            .is_ok()
+}
+
+// 형식만 확인 (두 글자 소문자)
+fn is_language_code(code: &str) -> bool {
+    code.len() == 2 && code.bytes().all(|b| b.is_ascii_lowercase())
+}
+
+/// 1-based line and column (in characters) of a byte offset.
+fn line_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+/// Byte offsets of the values (and top-level keys) of a JSON document, by
+/// path. Only run on text serde_json has already accepted.
+#[derive(Debug, Default)]
+struct Positions {
+    values: HashMap<String, usize>,
+    top_level_keys: Vec<(String, usize)>,
+}
+
+impl Positions {
+    fn scan(text: &str) -> Self {
+        let mut scanner = Scanner {
+            text: text.as_bytes(),
+            pos: 0,
+            positions: Positions::default(),
+        };
+        scanner.value(String::new());
+        scanner.positions
+    }
+
+    fn value(&self, path: &str) -> usize {
+        self.values.get(path).copied().unwrap_or_default()
+    }
+}
+
+struct Scanner<'a> {
+    text: &'a [u8],
+    pos: usize,
+    positions: Positions,
+}
+
+impl Scanner<'_> {
+    fn value(&mut self, path: String) {
+        self.skip_whitespace();
+        let start = self.pos;
+        match self.peek() {
+            Some(b'{') => {
+                self.pos += 1;
+                while let Some(byte) = self.next_member() {
+                    if byte != b'"' {
+                        continue;
+                    }
+                    let key_start = self.pos;
+                    let key = self.string();
+                    self.skip_whitespace();
+                    // ':'
+                    self.pos += 1;
+                    let child = if path.is_empty() {
+                        self.positions.top_level_keys.push((key.clone(), key_start));
+                        key
+                    } else {
+                        format!("{path}.{key}")
+                    };
+                    self.value(child);
+                }
+            }
+            Some(b'[') => {
+                self.pos += 1;
+                let mut index = 0;
+                while let Some(byte) = self.next_member() {
+                    if byte == b',' {
+                        continue;
+                    }
+                    self.value(format!("{path}[{index}]"));
+                    index += 1;
+                }
+            }
+            Some(b'"') => {
+                self.string();
+            }
+            _ => {
+                while self
+                    .peek()
+                    .is_some_and(|b| !matches!(b, b',' | b'}' | b']') && !b.is_ascii_whitespace())
+                {
+                    self.pos += 1;
+                }
+            }
+        }
+        self.positions.values.insert(path, start);
+    }
+
+    /// Skips to the next member of an object or array and returns its first
+    /// byte (a consumed `,` is returned as is), or `None` at the closing bracket.
+    fn next_member(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        let byte = self.peek()?;
+        match byte {
+            b'}' | b']' => {
+                self.pos += 1;
+                None
+            }
+            b',' => {
+                self.pos += 1;
+                Some(byte)
+            }
+            _ => Some(byte),
+        }
+    }
+
+    fn string(&mut self) -> String {
+        let start = self.pos;
+        self.pos += 1;
+        while let Some(byte) = self.peek() {
+            self.pos += if byte == b'\\' { 2 } else { 1 };
+            if byte == b'"' {
+                break;
+            }
+        }
+        let end = self.pos.min(self.text.len());
+        serde_json::from_slice(&self.text[start..end]).unwrap_or_default()
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|b| b.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problems(text: &str) -> Vec<String> {
+        let config: ClientsConfig = serde_json::from_str(text).unwrap();
+        check(text, &config)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_check_app() {
+        assert!(check_app(Store::AppStore, "284882215", "us").is_ok());
+        assert!(check_app(Store::PlayStore, "com.whatsapp", "KR").is_ok());
+
+        match check_app(Store::AppStore, "284882215", "usa").unwrap_err() {
+            error @ CrawlerError::Config { .. } => assert_eq!(
+                error.to_string(),
+                "Config error in `country`: \"usa\" is not an ISO 3166-1 alpha-2 country code"
+            ),
+            other => panic!("Expected Config, got {other:?}"),
+        }
+        assert!(matches!(
+            check_app(Store::PlayStore, "whatsapp", "us"),
+            Err(CrawlerError::Config { field: Some(field), .. }) if field == "app_id"
+        ));
+    }
+
+    #[test]
+    fn test_country_codes_are_sorted() {
+        assert!(COUNTRY_CODES.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(is_country_code("kr"));
+        assert!(is_country_code("US"));
+        assert!(!is_country_code("uk"));
+        assert!(!is_country_code("usa"));
+    }
+
+    #[test]
+    fn test_reports_every_problem_with_its_position() {
+        let text = r#"{
+  "app_store": [
+    { "app_id": "id1194408342", "country": "us" },
+    { "app_id": "284882215", "country": "xx" }
+  ],
+  "play_store": [
+    { "app_id": "com.whatsapp", "country": "us" },
+    { "app_id": "whatsapp", "country": "kr", "language": "korean" },
+    { "app_id": "com.whatsapp", "country": "US" }
+  ]
+}"#;
+
+        assert_eq!(
+            problems(text),
+            [
+                "`app_store[0].app_id` at line 3, column 17: \
+                 App Store ID must be numeric, drop the `id` prefix of \"id1194408342\"",
+                "`app_store[1].country` at line 4, column 41: \
+                 \"xx\" is not an ISO 3166-1 alpha-2 country code",
+                "`play_store[1].app_id` at line 8, column 17: \
+                 Play Store ID must be a package name like \"com.example.app\", got \"whatsapp\"",
+                "`play_store[1].language` at line 8, column 58: \
+                 \"korean\" is not an ISO 639-1 language code",
+                "`play_store[2]` at line 9, column 5: duplicate of `play_store[0]`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_top_level_keys() {
+        let text =
+            "{\n  \"app_store_apps\": [],\n  \"sinks\": [],\n  \"extra\": {\"a\": [1, \"]\"]}\n}";
+
+        assert_eq!(
+            problems(text),
+            [
+                "`app_store_apps` at line 2, column 3: unknown key, did you mean `app_store`?",
+                "`extra` at line 4, column 3: \
+                 unknown key, expected one of `app_store`, `play_store`, `sinks`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_error_position() {
+        let e = serde_json::from_str::<ClientsConfig>("{\n  \"app_store\": [1]\n}").unwrap_err();
+        let problem = ConfigProblem::from_json(&e);
+
+        assert_eq!((problem.line, problem.column), (2, 17));
+        assert!(!problem.message.contains("at line"));
+        assert_eq!(
+            ConfigProblems(vec![problem.clone(), problem])
+                .to_string()
+                .lines()
+                .count(),
+            3
+        );
+    }
+}
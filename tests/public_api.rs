@@ -0,0 +1,231 @@
+//! Embeds the crawler through the library API only, the way an external
+//! service would: a custom `TBuildRequest` client against a local mock server,
+//! fed through the stock App Store extractor.
+
+use std::time::Duration;
+
+use app_review_crawler::{
+    AppStoreExtractor, Crawler, CsvSink, HasAppInfo, ResponseProcessor, RetryPolicy, TBuildRequest,
+};
+use reqwest::RequestBuilder;
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+const FEED: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns:im="http://itunes.apple.com/rss" xmlns="http://www.w3.org/2005/Atom" xml:lang="en">
+    <entry>
+        <id>12645174720</id>
+        <title>Great idea but not well executed.</title>
+        <content type="text">The clothing choices are not accurate.</content>
+        <im:voteSum>1</im:voteSum>
+        <im:voteCount>3</im:voteCount>
+        <im:rating>2</im:rating>
+        <updated>2025-05-11T10:19:38-07:00</updated>
+        <im:version>7.2.3</im:version>
+        <author>
+            <name>Beegirl</name>
+            <uri>https://itunes.apple.com/us/reviews/id167338708</uri>
+        </author>
+    </entry>
+</feed>"#;
+
+const EMPTY_FEED: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns:im="http://itunes.apple.com/rss" xmlns="http://www.w3.org/2005/Atom"></feed>"#;
+
+struct MockFeedClient {
+    base_url: String,
+    page: u32,
+}
+
+impl HasAppInfo for MockFeedClient {
+    fn app_id(&self) -> &str {
+        "42"
+    }
+    fn country(&self) -> &str {
+        "us"
+    }
+}
+
+impl TBuildRequest for MockFeedClient {
+    fn build_request(&mut self) -> RequestBuilder {
+        reqwest::Client::new().get(format!("{}/page/{}", self.base_url, self.page))
+    }
+    fn has_more_pages(&self) -> bool {
+        self.page <= 5
+    }
+    fn next_page(&mut self, _response: &[u8]) {
+        self.page += 1;
+    }
+    fn skip_page(&mut self) -> bool {
+        self.page += 1;
+        true
+    }
+    fn get_current_page(&self) -> u32 {
+        self.page
+    }
+}
+
+#[tokio::test]
+async fn test_embedded_crawl_writes_csv() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/page/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(FEED))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/page/2"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(EMPTY_FEED))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = MockFeedClient {
+        base_url: server.uri(),
+        page: 1,
+    };
+    let crawler = Crawler::new(client).with_retry_policy(RetryPolicy {
+        max_attempts: 1,
+        base_delay: Duration::ZERO,
+        max_delay: Duration::ZERO,
+        jitter: false,
+        respect_retry_after: false,
+    });
+
+    let output = tempfile::tempdir().unwrap();
+    let extractor = AppStoreExtractor::new("42", "us");
+    let sink = CsvSink::append(output.path().join("app_store/42.csv")).unwrap();
+    let processor = ResponseProcessor::new(extractor).with_sink(Box::new(sink));
+    processor.run(crawler.into_pages()).await.unwrap();
+
+    // 빈 페이지(2페이지)에서 멈추므로 3페이지 이후는 요청하지 않음
+    let csv = std::fs::read_to_string(output.path().join("app_store/42.csv")).unwrap();
+    let mut lines = csv.lines();
+    assert_eq!(
+        lines.next(),
+        Some(
+            "store,app_id,country,review_id,date,rating,helpful_votes,total_votes,author,\
+             author_uri,version,language,title,body,reply_body,reply_date"
+        )
+    );
+    assert_eq!(
+        lines.next(),
+        Some(
+            "app_store,42,us,12645174720,2025-05-11T17:19:38Z,2,1,3,Beegirl,\
+             https://itunes.apple.com/us/reviews/id167338708,7.2.3,en,\
+             Great idea but not well executed.,The clothing choices are not accurate.,,"
+        )
+    );
+    assert_eq!(lines.next(), None);
+}
+
+#[tokio::test]
+async fn test_crawl_stops_at_first_empty_page() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/page/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(FEED))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/page/2"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(EMPTY_FEED))
+        .expect(1)
+        .mount(&server)
+        .await;
+    // 클라이언트는 5페이지까지 허용하지만 빈 페이지 뒤로는 요청하지 않음
+    Mock::given(method("GET"))
+        .and(path("/page/3"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(FEED.replace("12645174720", "12645174721")),
+        )
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let client = MockFeedClient {
+        base_url: server.uri(),
+        page: 1,
+    };
+    let crawler = Crawler::new(client).with_retry_policy(RetryPolicy {
+        max_attempts: 1,
+        base_delay: Duration::ZERO,
+        max_delay: Duration::ZERO,
+        jitter: false,
+        respect_retry_after: false,
+    });
+
+    let output = tempfile::tempdir().unwrap();
+    let extractor = AppStoreExtractor::new("42", "us");
+    let sink = CsvSink::append(output.path().join("app_store/42.csv")).unwrap();
+    let processor = ResponseProcessor::new(extractor).with_sink(Box::new(sink));
+    let (stats, result) = processor.run_with_stats(crawler.into_pages()).await;
+
+    result.unwrap();
+    assert_eq!(stats.pages, 2);
+    assert_eq!(stats.new_reviews, 1);
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_partial_success_skips_a_failing_page() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/page/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(FEED))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/page/2"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/page/3"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(FEED.replace("12645174720", "12645174721")),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/page/4"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(EMPTY_FEED))
+        .mount(&server)
+        .await;
+
+    let client = MockFeedClient {
+        base_url: server.uri(),
+        page: 1,
+    };
+    let crawler = Crawler::new(client).with_retry_policy(RetryPolicy {
+        max_attempts: 1,
+        base_delay: Duration::ZERO,
+        max_delay: Duration::ZERO,
+        jitter: false,
+        respect_retry_after: false,
+    });
+
+    let output = tempfile::tempdir().unwrap();
+    let extractor = AppStoreExtractor::new("42", "us");
+    let sink = CsvSink::append(output.path().join("app_store/42.csv")).unwrap();
+    let processor = ResponseProcessor::new(extractor)
+        .with_sink(Box::new(sink))
+        .with_partial_success();
+    let (stats, result) = processor.run_with_stats(crawler.into_pages()).await;
+
+    // 2페이지의 500 응답은 기록만 하고 3페이지부터 이어서 받음
+    let failed = result.unwrap();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].page, 2);
+    assert_eq!(failed[0].error.kind(), "http");
+    assert_eq!(stats.pages, 3);
+    assert_eq!(stats.new_reviews, 2);
+
+    let csv = std::fs::read_to_string(output.path().join("app_store/42.csv")).unwrap();
+    assert_eq!(csv.lines().count(), 3);
+}
@@ -0,0 +1,159 @@
+//! Runs the SQLite sink against a database file in a temp dir, opened the way
+//! the runner opens it: through `SinkConfig`.
+
+use std::path::Path;
+
+use app_review_crawler::{DeveloperReply, Review, SinkConfig, Store, TSink};
+use chrono::{TimeZone, Utc};
+use rusqlite::Connection;
+
+async fn open(db: &Path) -> Box<dyn TSink> {
+    let config = SinkConfig::Sqlite {
+        path: Some(db.to_path_buf()),
+    };
+    config
+        .open(
+            Path::new("unused"),
+            Store::PlayStore,
+            "com.example.app",
+            "us",
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_upserts_reviews_by_key() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = dir.path().join("reviews.db");
+
+    let mut sink = open(&db).await;
+    sink.write(&[Review::sample("1"), Review::sample("2")])
+        .await
+        .unwrap();
+    assert_eq!(sink.finish().await.unwrap(), 2);
+
+    // 수정된 리뷰는 새 행이 아니라 기존 행을 갱신
+    let edited = Review {
+        body: "works now".to_string(),
+        rating: 5,
+        developer_reply: Some(DeveloperReply {
+            body: "Thanks!".to_string(),
+            date: Some(Utc.timestamp_opt(1_747_000_000, 0).unwrap()),
+        }),
+        ..Review::sample("1")
+    };
+    let mut sink = open(&db).await;
+    sink.write(&[edited, Review::sample("3")]).await.unwrap();
+    assert_eq!(sink.finish().await.unwrap(), 1);
+
+    let conn = Connection::open(&db).unwrap();
+    let count: i64 = conn
+        .query_row("SELECT COUNT(*) FROM reviews", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(count, 3);
+
+    let (body, rating, reply_body, reply_date, date): (String, u8, String, String, String) = conn
+        .query_row(
+            "SELECT body, rating, reply_body, reply_date, date FROM reviews
+             WHERE store = 'play_store' AND app_id = 'com.example.app'
+               AND country = 'us' AND review_id = '1'",
+            [],
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                ))
+            },
+        )
+        .unwrap();
+    assert_eq!(body, "works now");
+    assert_eq!(rating, 5);
+    assert_eq!(reply_body, "Thanks!");
+    assert_eq!(reply_date, "2025-05-11T21:46:40Z");
+    assert_eq!(date, "2025-05-10T18:00:00Z");
+}
+
+#[tokio::test]
+async fn test_records_crawl_runs() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = dir.path().join("nested/reviews.db");
+
+    let mut sink = open(&db).await;
+    sink.write(&[Review::sample("1"), Review::sample("2")])
+        .await
+        .unwrap();
+    sink.write(&[Review::sample("3")]).await.unwrap();
+    // 빈 쓰기는 페이지로 세지 않음
+    sink.write(&[]).await.unwrap();
+    sink.finish().await.unwrap();
+
+    // finish 전에 중단된 실행은 finished_at이 비어 있음
+    let mut sink = open(&db).await;
+    sink.write(&[Review::sample("3")]).await.unwrap();
+    drop(sink);
+
+    let conn = Connection::open(&db).unwrap();
+    let mut statement = conn
+        .prepare(
+            "SELECT store, app_id, country, finished_at IS NOT NULL,
+                    pages, reviews_fetched, reviews_new
+             FROM crawl_runs ORDER BY id",
+        )
+        .unwrap();
+    let runs: Vec<(String, String, String, bool, i64, i64, i64)> = statement
+        .query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+                row.get(5)?,
+                row.get(6)?,
+            ))
+        })
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    let run = |finished, pages, fetched, new| {
+        (
+            "play_store".to_string(),
+            "com.example.app".to_string(),
+            "us".to_string(),
+            finished,
+            pages,
+            fetched,
+            new,
+        )
+    };
+    assert_eq!(runs, vec![run(true, 2, 3, 3), run(false, 0, 0, 0)]);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_locked_database_does_not_block_the_runtime() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = dir.path().join("reviews.db");
+    let mut sink = open(&db).await;
+
+    // 다른 연결이 쓰기 잠금을 잡고 있는 동안 write는 잠금이 풀리기를 기다림
+    let lock = Connection::open(&db).unwrap();
+    lock.execute_batch("BEGIN IMMEDIATE").unwrap();
+    let write = tokio::spawn(async move {
+        sink.write(&[Review::sample("1")]).await.unwrap();
+        sink.finish().await.unwrap()
+    });
+
+    // 스레드가 하나뿐인 런타임에서도 다른 작업은 계속 진행되어야 함
+    let started = std::time::Instant::now();
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    assert!(started.elapsed() < std::time::Duration::from_secs(5));
+    assert!(!write.is_finished());
+
+    lock.execute_batch("COMMIT").unwrap();
+    assert_eq!(write.await.unwrap(), 1);
+}